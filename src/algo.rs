@@ -42,6 +42,29 @@ const CRC16_TABLE: [u16; 256] = [
     0x3de3, 0x2c6a, 0x1ef1, 0x0f78,
 ];
 
+const CRC32_OGG_TABLE: [u32; 256] = crc32_ogg_table();
+
+const fn crc32_ogg_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80000000 != 0 {
+                (crc << 1) ^ 0x04c11db7
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
 pub fn crc8_calc(data: &[u8], base: Option<u8>) -> u8 {
     let mut crc = base.unwrap_or(0x77);
     for v in data.iter() {
@@ -60,6 +83,15 @@ pub fn crc16_calc(data: &[u8], base: Option<u16>) -> u16 {
     crc
 }
 
+pub fn crc32_ogg_calc(data: &[u8], base: Option<u32>) -> u32 {
+    let mut crc = base.unwrap_or(0);
+    for v in data.iter() {
+        crc = (crc << 8) ^ CRC32_OGG_TABLE[((crc >> 24) as u8 ^ *v) as usize]
+    }
+
+    crc
+}
+
 // pub fn simple_encrypt(data: &mut [u8]) {
 //     let mut key = 0x07u32;
 //     for v in data.iter_mut() {
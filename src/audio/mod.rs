@@ -0,0 +1,98 @@
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::Receiver;
+
+use crate::{conn::MediaStream, proto::v1::ctrl::StreamCtrlType, Error, Result, Robot};
+
+pub mod ogg;
+
+pub use ogg::OggOpusWriter;
+
+pub const AUDIO_STREAM_PORT: u16 = 40922;
+pub const AUDIO_SAMPLE_RATE: u32 = 48000;
+pub const AUDIO_CHANNELS: u8 = 1;
+
+const OPUS_PACKET_CHAN_SIZE: usize = 256;
+
+// tcp does not keep the boundaries of the pushed packets, so they have to be
+// recovered from the byte stream. The official sdk treats every read as one
+// packet, which only holds on an idle link.
+#[derive(Debug, Clone, Copy)]
+pub enum OpusFraming {
+    // constant bitrate packets of the given size, which must not be 0
+    Fixed(usize),
+}
+
+impl OpusFraming {
+    fn split(&self, pending: &mut Vec<u8>) -> Vec<Vec<u8>> {
+        let mut packets = Vec::new();
+        match *self {
+            Self::Fixed(size) => {
+                while pending.len() >= size {
+                    packets.push(pending.drain(..size).collect());
+                }
+            }
+        }
+
+        packets
+    }
+}
+
+#[derive(Debug)]
+pub struct OpusPacket {
+    pub data: Vec<u8>,
+    // time of arrival, relative to the start of the stream
+    pub timestamp: Duration,
+}
+
+pub struct AudioStream {
+    stream: MediaStream<OpusPacket>,
+}
+
+impl AudioStream {
+    pub fn start(robot: &Robot, ip: IpAddr, framing: OpusFraming) -> Result<Self> {
+        if let OpusFraming::Fixed(0) = framing {
+            return Err(Error::Other("opus packet size must not be 0".into()));
+        }
+
+        let epoch = Instant::now();
+        let mut pending = Vec::new();
+        let stream = MediaStream::start(
+            robot,
+            ip,
+            AUDIO_STREAM_PORT,
+            StreamCtrlType::Audio,
+            0,
+            OPUS_PACKET_CHAN_SIZE,
+            move |data: Option<&[u8]>, now: Instant| {
+                // a partial packet left at the end of the stream can not be decoded
+                let Some(data) = data else {
+                    return Ok(vec![]);
+                };
+
+                pending.extend_from_slice(data);
+                let timestamp = now.saturating_duration_since(epoch);
+                Ok(framing
+                    .split(&mut pending)
+                    .into_iter()
+                    .map(|data| OpusPacket { data, timestamp })
+                    .collect())
+            },
+        )?;
+
+        Ok(Self { stream })
+    }
+
+    pub fn receiver(&self) -> &Receiver<OpusPacket> {
+        self.stream.receiver()
+    }
+}
+
+impl Iterator for AudioStream {
+    type Item = OpusPacket;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stream.receiver().recv().ok()
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::Write;
+
+use crate::{algo::crc32_ogg_calc, Error, Result};
+
+const OGG_HEADER_CONTINUED: u8 = 0x01;
+const OGG_HEADER_BOS: u8 = 0x02;
+const OGG_HEADER_EOS: u8 = 0x04;
+const OGG_MAX_SEGMENTS: usize = 255;
+
+const OPUS_VENDOR: &[u8] = b"rbm-rs";
+
+// number of 48kHz samples in an opus packet, see RFC 6716 section 3.1
+pub fn opus_packet_samples(packet: &[u8]) -> Result<u32> {
+    crate::ensure_buf_size!(packet, 1, "opus toc");
+    let toc = packet[0];
+    let config = toc >> 3;
+
+    // frame size in 1/10 ms
+    let frame_size: u32 = match config {
+        0..=11 => [100, 200, 400, 600][config as usize % 4],
+        12..=15 => [100, 200][config as usize % 2],
+        _ => [25, 50, 100, 200][config as usize % 4],
+    };
+
+    let frames = match toc & 0x3 {
+        0 => 1,
+        1 | 2 => 2,
+        _ => {
+            crate::ensure_buf_size!(packet, 2, "opus frame count");
+            (packet[1] & 0x3f) as u32
+        }
+    };
+
+    Ok(frames * frame_size * 48 / 10)
+}
+
+// Muxes opus packets into an Ogg/Opus stream, one packet per page.
+pub struct OggOpusWriter<W: Write> {
+    w: W,
+    serial: u32,
+    page_seq: u32,
+    granule: u64,
+    pending: Option<Vec<u8>>,
+}
+
+impl<W: Write> OggOpusWriter<W> {
+    pub fn new(w: W, serial: u32, channels: u8, sample_rate: u32) -> Result<Self> {
+        let mut writer = Self {
+            w,
+            serial,
+            page_seq: 0,
+            granule: 0,
+            pending: None,
+        };
+
+        // see RFC 7845 section 5.1
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1);
+        head.push(channels);
+        head.extend_from_slice(&0u16.to_le_bytes());
+        head.extend_from_slice(&sample_rate.to_le_bytes());
+        head.extend_from_slice(&0i16.to_le_bytes());
+        head.push(0);
+        writer.write_page(&head, OGG_HEADER_BOS, 0)?;
+
+        // see RFC 7845 section 5.2
+        let mut tags = Vec::with_capacity(16 + OPUS_VENDOR.len());
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(OPUS_VENDOR.len() as u32).to_le_bytes());
+        tags.extend_from_slice(OPUS_VENDOR);
+        tags.extend_from_slice(&0u32.to_le_bytes());
+        writer.write_page(&tags, 0, 0)?;
+
+        Ok(writer)
+    }
+
+    pub fn write_packet(&mut self, packet: &[u8]) -> Result<()> {
+        // hold one packet back so that the last one can be flagged as end of stream
+        if let Some(prev) = self.pending.take() {
+            self.write_audio_page(&prev, 0)?;
+        }
+
+        self.pending = Some(packet.to_owned());
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        match self.pending.take() {
+            Some(last) => self.write_audio_page(&last, OGG_HEADER_EOS)?,
+            None => self.write_page(&[], OGG_HEADER_EOS, self.granule)?,
+        }
+
+        self.w.flush()?;
+        Ok(self.w)
+    }
+
+    fn write_audio_page(&mut self, packet: &[u8], header_type: u8) -> Result<()> {
+        self.granule += opus_packet_samples(packet)? as u64;
+        self.write_page(packet, header_type, self.granule)
+    }
+
+    fn write_page(&mut self, packet: &[u8], header_type: u8, granule: u64) -> Result<()> {
+        let segments = packet.len() / 255 + 1;
+        if segments > OGG_MAX_SEGMENTS {
+            return Err(Error::InvalidData(
+                format!(
+                    "packet of {} bytes is too large for an ogg page",
+                    packet.len()
+                )
+                .into(),
+            ));
+        }
+
+        let mut page = Vec::with_capacity(27 + segments + packet.len());
+        page.extend_from_slice(b"OggS");
+        page.push(0);
+        page.push(header_type & !OGG_HEADER_CONTINUED);
+        page.extend_from_slice(&granule.to_le_bytes());
+        page.extend_from_slice(&self.serial.to_le_bytes());
+        page.extend_from_slice(&self.page_seq.to_le_bytes());
+        page.extend_from_slice(&[0u8; 4]);
+        page.push(segments as u8);
+        page.extend(std::iter::repeat(255u8).take(segments - 1));
+        page.push((packet.len() % 255) as u8);
+        page.extend_from_slice(packet);
+
+        let crc = crc32_ogg_calc(&page, None);
+        page[22..26].copy_from_slice(&crc.to_le_bytes());
+
+        self.w.write_all(&page)?;
+        self.page_seq += 1;
+        Ok(())
+    }
+}
@@ -5,9 +5,11 @@ use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crossbeam_channel::{bounded, select, unbounded, Receiver, Sender, TryRecvError};
+use crossbeam_channel::{
+    bounded, select, unbounded, Receiver, RecvTimeoutError, Sender, TryRecvError,
+};
 use tracing::{debug, trace};
 
 use super::transport::Transport;
@@ -40,6 +42,50 @@ impl<R: Debug, S: Debug, E: Debug> ActionProgressRx<R, S, E> {
     }
 }
 
+pub struct EventRx<T> {
+    rx: Receiver<T>,
+    // handlers feeding `rx` are dropped once this is gone
+    _done: Sender<()>,
+    done_rx: Receiver<()>,
+}
+
+impl<T> EventRx<T> {
+    pub(crate) fn new(rx: Receiver<T>) -> Self {
+        let (done, done_rx) = bounded(0);
+        Self {
+            rx,
+            _done: done,
+            done_rx,
+        }
+    }
+
+    /// Disconnected once this receiver is dropped, see `Client::forward_event`.
+    pub(crate) fn alive(&self) -> Receiver<()> {
+        self.done_rx.clone()
+    }
+
+    pub fn next(&self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+
+    pub fn receiver(&self) -> &Receiver<T> {
+        &self.rx
+    }
+}
+
+struct EventHandler<C: Codec> {
+    ident: C::Ident,
+    // returns false once the receiver side is gone
+    hdl: Box<dyn Fn(&[u8]) -> Result<bool> + Send + Sync>,
+    done_rx: Receiver<()>,
+}
+
+impl<C: Codec> EventHandler<C> {
+    fn is_closed(&self) -> bool {
+        matches!(self.done_rx.try_recv(), Err(TryRecvError::Disconnected))
+    }
+}
+
 struct ActionProgressHandler<C: Codec> {
     cmd_id: (C::Ident, C::Seq),
     action_id: (C::Ident, C::Seq),
@@ -69,8 +115,13 @@ where
 {
     host: u8,
     target: u8,
-    cmd_tx: Sender<((C::Ident, C::Seq), Vec<u8>, Option<Sender<Vec<u8>>>)>,
+    cmd_tx: Sender<(
+        (C::Ident, C::Seq),
+        Vec<u8>,
+        Option<(Sender<Vec<u8>>, Option<Instant>)>,
+    )>,
     action_tx: Sender<(Vec<u8>, Arc<ActionProgressHandler<C>>)>,
+    event_tx: Sender<EventHandler<C>>,
     codec: Arc<C>,
     done_tx: Option<Sender<()>>,
     join: Option<thread::JoinHandle<()>>,
@@ -97,10 +148,18 @@ where
         let codec = Arc::new(C::default());
         let (cmd_tx, cmd_rx) = unbounded();
         let (action_tx, action_rx) = unbounded();
+        let (event_tx, event_rx) = unbounded();
         let (done_tx, done_rx) = bounded(0);
 
         let join = thread::spawn(move || {
-            start_client_inner::<T, C>(sender_trans, recv_trans, cmd_rx, action_rx, done_rx);
+            start_client_inner::<T, C>(
+                sender_trans,
+                recv_trans,
+                cmd_rx,
+                action_rx,
+                event_rx,
+                done_rx,
+            );
         });
 
         Ok(Self {
@@ -108,18 +167,50 @@ where
             target,
             cmd_tx,
             action_tx,
+            event_tx,
             codec,
             done_tx: Some(done_tx),
             join: Some(join),
         })
     }
 
+    pub fn host(&self) -> u8 {
+        self.host
+    }
+
     pub fn send_cmd<CMD>(
         &self,
         receiver: Option<u8>,
         cmd: CMD,
         need_ack: Option<DussMBAck>,
     ) -> Result<Option<CMD::Response>>
+    where
+        CMD: Command<Ident = C::Ident>,
+    {
+        self.send_cmd_inner(receiver, cmd, need_ack, None)
+    }
+
+    /// Like `send_cmd`, but gives up waiting for the response after `timeout`.
+    pub fn send_cmd_timeout<CMD>(
+        &self,
+        receiver: Option<u8>,
+        cmd: CMD,
+        need_ack: Option<DussMBAck>,
+        timeout: Duration,
+    ) -> Result<Option<CMD::Response>>
+    where
+        CMD: Command<Ident = C::Ident>,
+    {
+        self.send_cmd_inner(receiver, cmd, need_ack, Some(timeout))
+    }
+
+    fn send_cmd_inner<CMD>(
+        &self,
+        receiver: Option<u8>,
+        cmd: CMD,
+        need_ack: Option<DussMBAck>,
+        timeout: Option<Duration>,
+    ) -> Result<Option<CMD::Response>>
     where
         CMD: Command<Ident = C::Ident>,
     {
@@ -137,12 +228,22 @@ where
 
         let (resp_tx, resp_rx) = bounded(1);
         self.cmd_tx
-            .send(((CMD::IDENT, cmd_seq), data, Some(resp_tx)))
+            .send((
+                (CMD::IDENT, cmd_seq),
+                data,
+                Some((resp_tx, timeout.map(|t| Instant::now() + t))),
+            ))
             .map_err(|_| Error::Other("sending chan broken".into()))?;
 
-        let resp_data = resp_rx
-            .recv()
-            .map_err(|_| Error::Other("response chan broken".into()))?;
+        let resp_data = match timeout {
+            Some(timeout) => resp_rx.recv_timeout(timeout).map_err(|e| match e {
+                RecvTimeoutError::Timeout => Error::Other("response timeout".into()),
+                RecvTimeoutError::Disconnected => Error::Other("response chan broken".into()),
+            })?,
+            None => resp_rx
+                .recv()
+                .map_err(|_| Error::Other("response chan broken".into()))?,
+        };
 
         <CMD as Command>::Response::de(&resp_data[..]).map(Some)
     }
@@ -198,6 +299,49 @@ where
             _done: done_tx,
         })
     }
+
+    pub fn subscribe_event<E, T, F>(&self, map: F) -> Result<EventRx<T>>
+    where
+        E: Event<Ident = C::Ident>,
+        T: Send + 'static,
+        F: Fn(E) -> Option<T> + Send + Sync + 'static,
+    {
+        let (tx, rx) = unbounded();
+        let rx = EventRx::new(rx);
+        self.forward_event(tx, rx.alive(), map)?;
+        Ok(rx)
+    }
+
+    /// Like `subscribe_event`, but delivers into an existing channel so that
+    /// several event kinds can be merged into one stream. The handler is
+    /// removed once `alive` is disconnected, see `EventRx::alive`.
+    pub(crate) fn forward_event<E, T, F>(
+        &self,
+        tx: Sender<T>,
+        alive: Receiver<()>,
+        map: F,
+    ) -> Result<()>
+    where
+        E: Event<Ident = C::Ident>,
+        T: Send + 'static,
+        F: Fn(E) -> Option<T> + Send + Sync + 'static,
+    {
+        let hdl: EventHandler<C> = EventHandler {
+            ident: E::IDENT,
+            hdl: Box::new(move |data| {
+                let evt = E::de(data)?;
+                match map(evt) {
+                    Some(v) => Ok(tx.send(v).is_ok()),
+                    None => Ok(true),
+                }
+            }),
+            done_rx: alive,
+        };
+
+        self.event_tx
+            .send(hdl)
+            .map_err(|_| Error::Other("sending chan broken".into()))
+    }
 }
 
 impl<C> Drop for Client<C>
@@ -216,8 +360,13 @@ where
 fn start_client_inner<T, C>(
     mut sender_trans: T,
     mut recv_trans: T,
-    cmd_rx: Receiver<((C::Ident, C::Seq), Vec<u8>, Option<Sender<Vec<u8>>>)>,
+    cmd_rx: Receiver<(
+        (C::Ident, C::Seq),
+        Vec<u8>,
+        Option<(Sender<Vec<u8>>, Option<Instant>)>,
+    )>,
     action_rx: Receiver<(Vec<u8>, Arc<ActionProgressHandler<C>>)>,
+    event_rx: Receiver<EventHandler<C>>,
     done: Receiver<()>,
 ) where
     T: Transport,
@@ -234,6 +383,7 @@ fn start_client_inner<T, C>(
                 &mut sender_trans,
                 cmd_rx,
                 action_rx,
+                event_rx,
                 recv_raw_rx,
                 recv_done_rx,
             ) {
@@ -257,8 +407,13 @@ fn start_client_inner<T, C>(
 fn start_client_dispatch<T, C>(
     done: Receiver<()>,
     trans: &mut T,
-    cmd_rx: Receiver<((C::Ident, C::Seq), Vec<u8>, Option<Sender<Vec<u8>>>)>,
+    cmd_rx: Receiver<(
+        (C::Ident, C::Seq),
+        Vec<u8>,
+        Option<(Sender<Vec<u8>>, Option<Instant>)>,
+    )>,
     action_rx: Receiver<(Vec<u8>, Arc<ActionProgressHandler<C>>)>,
+    event_rx: Receiver<EventHandler<C>>,
     raw_tx: Receiver<((C::Ident, C::Seq), C::Ctx, Vec<u8>)>,
     recv_loop_done: Receiver<()>,
 ) -> Result<()>
@@ -269,14 +424,16 @@ where
     let mut pending_cmds = HashMap::new();
     let mut pending_action_resp_hdls = HashMap::new();
     let mut pending_action_event_hdls = HashMap::new();
+    let mut event_hdls: HashMap<C::Ident, Vec<EventHandler<C>>> = HashMap::new();
     let mut sent_cmd = 0;
     let mut sent_action = 0;
     let mut recv_resp = 0;
     let mut recv_action_event = 0;
+    let mut recv_event = 0;
     'DISPATCH_LOOP: loop {
         debug!(
             sent_cmd,
-            sent_action, recv_resp, recv_action_event, "waiting for client events"
+            sent_action, recv_resp, recv_action_event, recv_event, "waiting for client events"
         );
         select! {
             recv(done) -> _ => {
@@ -292,8 +449,13 @@ where
                 trace!(?data, "cmd data");
                 trans.send(&data[..])?;
                 debug!(?msg_id, size = data.len(), pending = maybe_resp.is_some(), "cmd data sent");
-                if let Some(resp_tx) = maybe_resp {
-                    pending_cmds.insert(msg_id, resp_tx);
+                if let Some((resp_tx, deadline)) = maybe_resp {
+                    // callers that gave up waiting leave their entries behind
+                    let now = Instant::now();
+                    pending_cmds.retain(|_, (_, deadline): &mut (Sender<Vec<u8>>, Option<Instant>)| {
+                        deadline.map_or(true, |d| d > now)
+                    });
+                    pending_cmds.insert(msg_id, (resp_tx, deadline));
                 }
 
                 sent_cmd += 1;
@@ -311,10 +473,22 @@ where
                 sent_action += 1;
             }
 
+            recv(event_rx) -> event_res => {
+                let hdl = event_res.map_err(|_| Error::Other("event chan broken".into()))?;
+                debug!(ident = ?hdl.ident, "event handler registered");
+                // handlers of idents that are no longer pushed are only
+                // pruned here
+                event_hdls.retain(|_, hdls| {
+                    hdls.retain(|hdl| !hdl.is_closed());
+                    !hdls.is_empty()
+                });
+                event_hdls.entry(hdl.ident).or_default().push(hdl);
+            }
+
             recv(raw_tx) -> raw_res => {
                 let (msg_id, _msg_ctx, raw_data) = raw_res.map_err(|_| Error::Other("raw response chan broken".into()))?;
                 trace!(?raw_data, "recv raw data");
-                if let Some(tx) = pending_cmds.remove(&msg_id) {
+                if let Some((tx, _)) = pending_cmds.remove(&msg_id) {
                     if let Err(_e) = tx.send(raw_data) {
                         // TODO: logging
                     }
@@ -334,31 +508,43 @@ where
                 }
 
                 // try action event
-                match C::unpack_action_status(&raw_data) {
-                    Ok((action_seq, status, used)) => {
-                        if let Some(hdl) = pending_action_event_hdls.get(&(msg_id.0, action_seq)) {
-                            if let Err(_e) = hdl.try_send_event(status, &raw_data[used..]) {
-                                // TODO: logging
-                            }
+                if let Ok((action_seq, status, used)) = C::unpack_action_status(&raw_data) {
+                    if let Some(hdl) = pending_action_event_hdls.get(&(msg_id.0, action_seq)) {
+                        if let Err(_e) = hdl.try_send_event(status, &raw_data[used..]) {
+                            // TODO: logging
                         }
 
                         recv_action_event += 1;
                         continue 'DISPATCH_LOOP;
-                    },
-
-                    Err(_e) => {
-                        // TODO: logging
                     }
                 }
 
+                // dispatch subscribed events
+                if let Some(hdls) = event_hdls.get_mut(&msg_id.0) {
+                    hdls.retain(|hdl| !hdl.is_closed() && match (hdl.hdl)(&raw_data) {
+                        Ok(alive) => alive,
+                        Err(e) => {
+                            debug!(?msg_id, "invalid event data: {:?}", e);
+                            true
+                        }
+                    });
+
+                    if hdls.is_empty() {
+                        event_hdls.remove(&msg_id.0);
+                    }
 
-                // TODO: dispatch subsribed events
+                    recv_event += 1;
+                }
             }
 
             // clenup
             default(Duration::from_secs(300)) => {
                 pending_action_resp_hdls.retain(|_, hdl: &mut Arc<ActionProgressHandler<C>>| !hdl.as_ref().is_closed());
                 pending_action_event_hdls.retain(|_, hdl: &mut Arc<ActionProgressHandler<C>>| !hdl.as_ref().is_closed());
+                event_hdls.retain(|_, hdls| {
+                    hdls.retain(|hdl| !hdl.is_closed());
+                    !hdls.is_empty()
+                });
             }
         }
     }
@@ -1,7 +1,9 @@
 mod client;
+mod stream;
 mod transport;
 
-pub use client::Client;
+pub use client::{ActionProgressRx, Client, EventRx};
+pub(crate) use stream::MediaStream;
 pub use transport::{Tcp, Transport, Udp};
 
 #[derive(Debug, Clone, Copy)]
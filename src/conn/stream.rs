@@ -0,0 +1,166 @@
+use std::io::Read;
+use std::net::{IpAddr, Shutdown, SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel::{bounded, select, Receiver, Sender};
+use tracing::debug;
+
+use crate::{
+    conn::Client,
+    proto::{
+        host2byte,
+        v1::{
+            ctrl::{StreamCtrl, StreamCtrlType},
+            V1,
+        },
+    },
+    Result, Robot,
+};
+
+const STREAM_READ_BUF_SIZE: usize = 64 * 1024;
+
+// A media stream pushed by the robot over a dedicated tcp port. The bytes
+// read are handed to a decoder, which gets `None` once the stream ends so
+// that it can flush what it still holds.
+pub(crate) struct MediaStream<T> {
+    host: u8,
+    client: Arc<Client<V1>>,
+    typ: StreamCtrlType,
+    conn: TcpStream,
+    rx: Receiver<T>,
+    done_tx: Option<Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> MediaStream<T> {
+    pub(crate) fn start<D>(
+        robot: &Robot,
+        ip: IpAddr,
+        port: u16,
+        typ: StreamCtrlType,
+        resolution: u8,
+        chan_size: usize,
+        decoder: D,
+    ) -> Result<Self>
+    where
+        D: FnMut(Option<&[u8]>, Instant) -> Result<Vec<T>> + Send + 'static,
+    {
+        let host = host2byte(1, 0);
+        let client = robot.client().clone();
+
+        client.send_cmd(
+            Some(host),
+            StreamCtrl {
+                ctrl: StreamCtrlType::SdkConn as u8,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        client.send_cmd(
+            Some(host),
+            StreamCtrl {
+                ctrl: typ as u8,
+                resolution,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        let dest = SocketAddr::new(ip, port);
+        debug!(?dest, ?typ, "connecting media stream");
+        let conn = TcpStream::connect(dest)?;
+        let recv_conn = conn.try_clone()?;
+
+        let (tx, rx) = bounded(chan_size);
+        let (done_tx, done_rx) = bounded(0);
+        let join = thread::spawn(move || {
+            debug!(?typ, "media recv loop start");
+            if let Err(e) = start_media_recv(recv_conn, decoder, tx, done_rx) {
+                debug!(?typ, "media recv loop broken: {:?}", e);
+            }
+            debug!(?typ, "media recv loop stop");
+        });
+
+        Ok(Self {
+            host,
+            client,
+            typ,
+            conn,
+            rx,
+            done_tx: Some(done_tx),
+            join: Some(join),
+        })
+    }
+
+    pub(crate) fn receiver(&self) -> &Receiver<T> {
+        &self.rx
+    }
+}
+
+impl<T> Drop for MediaStream<T> {
+    fn drop(&mut self) {
+        drop(self.done_tx.take());
+        let _ = self.conn.shutdown(Shutdown::Both);
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+
+        if let Err(e) = self.client.send_cmd(
+            Some(self.host),
+            StreamCtrl {
+                ctrl: self.typ as u8,
+                state: 0,
+                ..Default::default()
+            },
+            None,
+        ) {
+            debug!(typ = ?self.typ, "failed to turn off media stream: {:?}", e);
+        }
+    }
+}
+
+fn start_media_recv<T, D>(
+    mut conn: TcpStream,
+    mut decoder: D,
+    tx: Sender<T>,
+    done: Receiver<()>,
+) -> Result<()>
+where
+    D: FnMut(Option<&[u8]>, Instant) -> Result<Vec<T>>,
+{
+    let mut buf = vec![0u8; STREAM_READ_BUF_SIZE];
+    loop {
+        // a broken connection ends the stream just like eof, the decoder
+        // still gets to flush before the error is returned
+        let (read, err) = match conn.read(&mut buf[..]) {
+            Ok(read) => (read, None),
+            Err(e) => (0, Some(e)),
+        };
+        let items = decoder((read > 0).then(|| &buf[..read]), Instant::now())?;
+
+        for item in items {
+            select! {
+                send(tx, item) -> res => {
+                    if res.is_err() {
+                        return Ok(());
+                    }
+                }
+
+                recv(done) -> _ => {
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Some(e) = err {
+            return Err(e.into());
+        }
+
+        if read == 0 {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Client side motion control: closed-loop controllers driving the chassis
+//! from a pose estimate, see `odometry`, and velocity profiles.
+
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
+
+use crate::{
+    kinematics::Twist,
+    odometry::{Pose, PoseEstimate},
+    proto::action::State,
+};
+
+pub mod path;
+pub mod pid;
+pub mod pose;
+pub mod profile;
+
+pub use path::{HeadingMode, PathFollower, PathFollowerConfig};
+pub use pid::{Pid, PidGains};
+pub use pose::{GoToPose, GoToPoseConfig};
+pub use profile::{ChassisProfiler, GimbalProfiler, MotionLimits, Ramp, SCurve, Trapezoid};
+
+/// Progress of a controller, reported like the status of a firmware action.
+#[derive(Debug, Clone, Copy)]
+pub struct ControlStatus {
+    pub percent: u8,
+    pub state: State,
+    pub pose: Pose,
+}
+
+impl Default for ControlStatus {
+    fn default() -> Self {
+        Self {
+            percent: 0,
+            state: State::Idle,
+            pose: Default::default(),
+        }
+    }
+}
+
+fn report(progress_tx: &Option<Sender<ControlStatus>>, status: ControlStatus) {
+    if let Some(tx) = progress_tx {
+        let _ = tx.send(status);
+    }
+}
+
+/// Waits for a pose estimate and skips to the newest queued one, so that a
+/// slow control loop never steers on a stale backlog.
+fn latest_pose(
+    poses: &Receiver<PoseEstimate>,
+    timeout: Duration,
+) -> Result<PoseEstimate, RecvTimeoutError> {
+    let first = poses.recv_timeout(timeout)?;
+    Ok(poses.try_iter().last().unwrap_or(first))
+}
+
+/// Rotates a world frame vector into the body frame of `pose`.
+fn world_to_body(pose: &Pose, x: f32, y: f32) -> (f32, f32) {
+    let (sin, cos) = pose.theta.to_radians().sin_cos();
+    (x * cos + y * sin, -x * sin + y * cos)
+}
+
+/// Limits the change from `prev` to `next` to the given accelerations.
+fn limit_accel(prev: Twist, next: Twist, accel_xy: f32, accel_z: f32, dt: f32) -> Twist {
+    let (dx, dy) = (next.x - prev.x, next.y - prev.y);
+    let dxy = (dx * dx + dy * dy).sqrt();
+    let max_dxy = accel_xy * dt;
+    let k = if dxy > max_dxy { max_dxy / dxy } else { 1.0 };
+
+    let max_dz = accel_z * dt;
+    Twist {
+        x: prev.x + dx * k,
+        y: prev.y + dy * k,
+        z: prev.z + (next.z - prev.z).clamp(-max_dz, max_dz),
+    }
+}
@@ -0,0 +1,257 @@
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender};
+use tracing::debug;
+
+use super::{latest_pose, limit_accel, report, world_to_body, ControlStatus};
+use crate::{
+    kinematics::Twist,
+    modules::chassis::Chassis,
+    odometry::{wrap_degrees, PoseEstimate},
+    proto::action::State,
+    Error, Result,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum HeadingMode {
+    /// hold the given heading in degrees, driving sideways as needed
+    Fixed(f32),
+    /// turn to face the direction of travel
+    AlongPath,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PathFollowerConfig {
+    /// m, distance ahead on the path to steer towards
+    pub lookahead: f32,
+    /// m/s
+    pub cruise_speed: f32,
+    /// m/s²
+    pub max_accel_xy: f32,
+    /// m/s², used to slow down for the end of the path
+    pub max_decel_xy: f32,
+    /// °/s
+    pub max_speed_z: f32,
+    /// °/s²
+    pub max_accel_z: f32,
+    /// 1/s, commanded rate per degree of heading error
+    pub kp_z: f32,
+    pub heading: HeadingMode,
+    /// m, distance to the last waypoint to consider the path done
+    pub goal_tolerance: f32,
+    pub timeout: Duration,
+}
+
+impl Default for PathFollowerConfig {
+    fn default() -> Self {
+        Self {
+            lookahead: 0.3,
+            cruise_speed: 0.5,
+            max_accel_xy: 1.0,
+            max_decel_xy: 0.8,
+            max_speed_z: 90.0,
+            max_accel_z: 180.0,
+            kp_z: 3.0,
+            heading: HeadingMode::AlongPath,
+            goal_tolerance: 0.03,
+            timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// Pure pursuit over a polyline, driving the mecanum base holonomically
+/// towards a point `lookahead` ahead of its projection on the path.
+pub struct PathFollower {
+    points: Vec<(f32, f32)>,
+    // arc length at each point
+    lengths: Vec<f32>,
+    cfg: PathFollowerConfig,
+    progress_tx: Option<Sender<ControlStatus>>,
+
+    pub status: ControlStatus,
+}
+
+impl PathFollower {
+    /// `waypoints` are (x, y) in m in the odometry frame.
+    pub fn new(waypoints: Vec<(f32, f32)>, cfg: PathFollowerConfig) -> Result<Self> {
+        if waypoints.is_empty() {
+            return Err(Error::Other("empty path".into()));
+        }
+
+        let mut lengths = Vec::with_capacity(waypoints.len());
+        let mut total = 0.0;
+        lengths.push(total);
+        for w in waypoints.windows(2) {
+            total += dist(w[0], w[1]);
+            lengths.push(total);
+        }
+
+        Ok(Self {
+            points: waypoints,
+            lengths,
+            cfg,
+            progress_tx: None,
+            status: Default::default(),
+        })
+    }
+
+    /// Status updates sent while `run` is in progress.
+    pub fn subscribe_progress(&mut self) -> Receiver<ControlStatus> {
+        let (tx, rx) = unbounded();
+        self.progress_tx = Some(tx);
+        rx
+    }
+
+    fn total(&self) -> f32 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    /// Projects `p` onto the path from segment `from` on, returning the
+    /// segment index and arc length of the closest point. Segments starting
+    /// beyond `max_s` are skipped, so a route passing near itself, like a
+    /// closed patrol loop, isn't cut short.
+    fn project(&self, p: (f32, f32), from: usize, max_s: f32) -> (usize, f32) {
+        let mut best = (from, self.lengths[from], f32::MAX);
+        for i in from..self.points.len().saturating_sub(1) {
+            if self.lengths[i] > max_s {
+                break;
+            }
+
+            let (a, b) = (self.points[i], self.points[i + 1]);
+            let seg_len = self.lengths[i + 1] - self.lengths[i];
+            let t = if seg_len > f32::EPSILON {
+                (((p.0 - a.0) * (b.0 - a.0) + (p.1 - a.1) * (b.1 - a.1)) / (seg_len * seg_len))
+                    .clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            let q = (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+            let d = dist(p, q);
+            if d < best.2 {
+                best = (i, self.lengths[i] + seg_len * t, d);
+            }
+        }
+
+        (best.0, best.1)
+    }
+
+    /// The point at arc length `s`, clamped to the path ends.
+    fn point_at(&self, s: f32) -> (f32, f32) {
+        let i = match self.lengths.iter().position(|l| *l > s) {
+            Some(0) => return self.points[0],
+            Some(i) => i,
+            None => return self.points[self.points.len() - 1],
+        };
+
+        let (a, b) = (self.points[i - 1], self.points[i]);
+        let t = (s - self.lengths[i - 1]) / (self.lengths[i] - self.lengths[i - 1]);
+        (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+    }
+
+    /// Blocks until the end of the path is reached, returning an error if it
+    /// times out. The chassis is stopped either way.
+    pub fn run(&mut self, chassis: &Chassis, poses: &Receiver<PoseEstimate>) -> Result<()> {
+        let guard = chassis.drive_guard();
+        let deadline = Instant::now() + self.cfg.timeout;
+        let goal = self.points[self.points.len() - 1];
+        let total = self.total();
+        let mut seg = 0;
+        let mut s = 0.0;
+        let mut cmd = Twist::default();
+        let mut last = Instant::now();
+
+        self.status.state = State::Started;
+        report(&self.progress_tx, self.status);
+
+        loop {
+            let remain = deadline.saturating_duration_since(Instant::now());
+            let pose = match latest_pose(poses, remain) {
+                Ok(estimate) => estimate.pose,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.status.state = State::Failed;
+                    report(&self.progress_tx, self.status);
+                    // the stop failing must not hide why the run ended
+                    if let Err(e) = guard.stop() {
+                        debug!("failed to stop chassis: {:?}", e);
+                    }
+                    return Err(Error::Other(
+                        format!("path not finished within {:?}", self.cfg.timeout).into(),
+                    ));
+                }
+
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.status.state = State::Aborted;
+                    report(&self.progress_tx, self.status);
+                    if let Err(e) = guard.stop() {
+                        debug!("failed to stop chassis: {:?}", e);
+                    }
+                    return Err(Error::Other("pose estimate chan broken".into()));
+                }
+            };
+
+            let now = Instant::now();
+            let dt = (now - last).as_secs_f32();
+            last = now;
+
+            let here = (pose.x, pose.y);
+            (seg, s) = self.project(here, seg, s + 2.0 * self.cfg.lookahead);
+
+            self.status.pose = pose;
+            self.status.percent = if total > f32::EPSILON {
+                ((s / total).clamp(0.0, 1.0) * 100.0) as u8
+            } else {
+                100
+            };
+
+            let to_goal = dist(here, goal);
+            if to_goal <= self.cfg.goal_tolerance && total - s <= self.cfg.goal_tolerance {
+                self.status.percent = 100;
+                self.status.state = State::Succeeded;
+                report(&self.progress_tx, self.status);
+                return guard.stop();
+            }
+
+            self.status.state = State::Running;
+            report(&self.progress_tx, self.status);
+
+            let target = self.point_at(s + self.cfg.lookahead);
+            let (ex, ey) = (target.0 - here.0, target.1 - here.1);
+            let d = (ex * ex + ey * ey).sqrt();
+
+            // remaining distance along the path, for braking before the end
+            let left = d + (total - (s + self.cfg.lookahead)).max(0.0);
+            let speed = self
+                .cfg
+                .cruise_speed
+                .min((2.0 * self.cfg.max_decel_xy * left).sqrt());
+            let (vx, vy) = if d > f32::EPSILON {
+                (ex / d * speed, ey / d * speed)
+            } else {
+                (0.0, 0.0)
+            };
+
+            let heading = match self.cfg.heading {
+                HeadingMode::Fixed(theta) => theta,
+                HeadingMode::AlongPath if d > f32::EPSILON => ey.atan2(ex).to_degrees(),
+                HeadingMode::AlongPath => pose.theta,
+            };
+            let z = (self.cfg.kp_z * wrap_degrees(heading - pose.theta))
+                .clamp(-self.cfg.max_speed_z, self.cfg.max_speed_z);
+
+            let (bx, by) = world_to_body(&pose, vx, vy);
+            cmd = limit_accel(
+                cmd,
+                Twist { x: bx, y: by, z },
+                self.cfg.max_accel_xy,
+                self.cfg.max_accel_z,
+                dt,
+            );
+            guard.drive_speed(cmd.x, cmd.y, cmd.z)?;
+        }
+    }
+}
+
+fn dist(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt()
+}
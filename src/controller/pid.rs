@@ -0,0 +1,54 @@
+#[derive(Debug, Clone, Copy)]
+pub struct PidGains {
+    pub kp: f32,
+    pub ki: f32,
+    pub kd: f32,
+    /// bound of the accumulated integral term, guards against windup
+    pub integral_limit: f32,
+}
+
+impl PidGains {
+    pub fn p(kp: f32) -> Self {
+        Self {
+            kp,
+            ki: 0.0,
+            kd: 0.0,
+            integral_limit: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pid {
+    gains: PidGains,
+    integral: f32,
+    prev_err: Option<f32>,
+}
+
+impl Pid {
+    pub fn new(gains: PidGains) -> Self {
+        Self {
+            gains,
+            integral: 0.0,
+            prev_err: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.prev_err = None;
+    }
+
+    pub fn update(&mut self, err: f32, dt: f32) -> f32 {
+        let g = &self.gains;
+        self.integral =
+            (self.integral + g.ki * err * dt).clamp(-g.integral_limit, g.integral_limit);
+
+        let deriv = match self.prev_err.replace(err) {
+            Some(prev) if dt > 0.0 => (err - prev) / dt,
+            _ => 0.0,
+        };
+
+        g.kp * err + self.integral + g.kd * deriv
+    }
+}
@@ -0,0 +1,161 @@
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender};
+use tracing::debug;
+
+use super::{latest_pose, limit_accel, report, world_to_body, ControlStatus};
+use crate::{
+    kinematics::Twist,
+    modules::chassis::Chassis,
+    odometry::{wrap_degrees, Pose, PoseEstimate},
+    proto::action::State,
+    Error, Result,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct GoToPoseConfig {
+    /// m/s
+    pub max_speed_xy: f32,
+    /// °/s
+    pub max_speed_z: f32,
+    /// m/s²
+    pub max_accel_xy: f32,
+    /// °/s²
+    pub max_accel_z: f32,
+    /// 1/s, commanded speed per m of distance left
+    pub kp_xy: f32,
+    /// 1/s, commanded rate per degree of heading left
+    pub kp_z: f32,
+    /// m
+    pub tolerance_xy: f32,
+    /// degrees
+    pub tolerance_z: f32,
+    pub timeout: Duration,
+}
+
+impl Default for GoToPoseConfig {
+    fn default() -> Self {
+        Self {
+            max_speed_xy: 0.5,
+            max_speed_z: 90.0,
+            max_accel_xy: 1.0,
+            max_accel_z: 180.0,
+            kp_xy: 2.0,
+            kp_z: 3.0,
+            tolerance_xy: 0.02,
+            tolerance_z: 2.0,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Drives the chassis to an absolute pose in the odometry frame, commanding
+/// `ChassisSpeedMode` at the rate pose estimates arrive.
+pub struct GoToPose {
+    goal: Pose,
+    cfg: GoToPoseConfig,
+    progress_tx: Option<Sender<ControlStatus>>,
+
+    pub status: ControlStatus,
+}
+
+impl GoToPose {
+    pub fn new(goal: Pose, cfg: GoToPoseConfig) -> Self {
+        Self {
+            goal,
+            cfg,
+            progress_tx: None,
+            status: Default::default(),
+        }
+    }
+
+    /// Status updates sent while `run` is in progress.
+    pub fn subscribe_progress(&mut self) -> Receiver<ControlStatus> {
+        let (tx, rx) = unbounded();
+        self.progress_tx = Some(tx);
+        rx
+    }
+
+    /// Blocks until the goal is reached within tolerance, returning an error
+    /// if it times out. The chassis is stopped either way.
+    pub fn run(&mut self, chassis: &Chassis, poses: &Receiver<PoseEstimate>) -> Result<()> {
+        let guard = chassis.drive_guard();
+        let deadline = Instant::now() + self.cfg.timeout;
+        let mut start_dist = None;
+        let mut cmd = Twist::default();
+        let mut last = Instant::now();
+
+        self.status.state = State::Started;
+        report(&self.progress_tx, self.status);
+
+        loop {
+            let remain = deadline.saturating_duration_since(Instant::now());
+            let pose = match latest_pose(poses, remain) {
+                Ok(estimate) => estimate.pose,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.status.state = State::Failed;
+                    report(&self.progress_tx, self.status);
+                    // the stop failing must not hide why the run ended
+                    if let Err(e) = guard.stop() {
+                        debug!("failed to stop chassis: {:?}", e);
+                    }
+                    return Err(Error::Other(
+                        format!("pose not reached within {:?}", self.cfg.timeout).into(),
+                    ));
+                }
+
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.status.state = State::Aborted;
+                    report(&self.progress_tx, self.status);
+                    if let Err(e) = guard.stop() {
+                        debug!("failed to stop chassis: {:?}", e);
+                    }
+                    return Err(Error::Other("pose estimate chan broken".into()));
+                }
+            };
+
+            let now = Instant::now();
+            let dt = (now - last).as_secs_f32();
+            last = now;
+
+            let (ex, ey) = (self.goal.x - pose.x, self.goal.y - pose.y);
+            let dist = (ex * ex + ey * ey).sqrt();
+            let etheta = wrap_degrees(self.goal.theta - pose.theta);
+
+            let start = *start_dist.get_or_insert(dist.max(f32::EPSILON));
+            self.status.pose = pose;
+            self.status.percent = ((1.0 - dist / start).clamp(0.0, 1.0) * 100.0) as u8;
+
+            if dist <= self.cfg.tolerance_xy && etheta.abs() <= self.cfg.tolerance_z {
+                self.status.percent = 100;
+                self.status.state = State::Succeeded;
+                report(&self.progress_tx, self.status);
+                return guard.stop();
+            }
+
+            self.status.state = State::Running;
+            report(&self.progress_tx, self.status);
+
+            // proportional, capped by the speed we can still brake from
+            let speed = (self.cfg.kp_xy * dist)
+                .min(self.cfg.max_speed_xy)
+                .min((2.0 * self.cfg.max_accel_xy * dist).sqrt());
+            let (vx, vy) = if dist > f32::EPSILON {
+                (ex / dist * speed, ey / dist * speed)
+            } else {
+                (0.0, 0.0)
+            };
+            let (bx, by) = world_to_body(&pose, vx, vy);
+            let z = (self.cfg.kp_z * etheta).clamp(-self.cfg.max_speed_z, self.cfg.max_speed_z);
+
+            cmd = limit_accel(
+                cmd,
+                Twist { x: bx, y: by, z },
+                self.cfg.max_accel_xy,
+                self.cfg.max_accel_z,
+                dt,
+            );
+            guard.drive_speed(cmd.x, cmd.y, cmd.z)?;
+        }
+    }
+}
@@ -0,0 +1,496 @@
+use crate::{
+    kinematics::Twist,
+    modules::{chassis::Chassis, gimbal::Gimbal},
+    util::unit_convertor::{
+        UnitConvertor, CHASSIS_SPD_X_CONVERTOR, CHASSIS_SPD_Y_CONVERTOR, CHASSIS_SPD_Z_CONVERTOR,
+        GIMBAL_PITCH_SPEED_CONVERTOR, GIMBAL_YAW_SPEED_CONVERTOR,
+    },
+    Error, Result,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct MotionLimits {
+    pub max_vel: f32,
+    pub max_accel: f32,
+    /// `None` gives trapezoidal profiles, `Some` S-curves
+    pub max_jerk: Option<f32>,
+}
+
+impl MotionLimits {
+    pub fn new(max_vel: f32, max_accel: f32, max_jerk: Option<f32>) -> Result<Self> {
+        let limits = Self {
+            max_vel,
+            max_accel,
+            max_jerk,
+        };
+        limits.check()?;
+        Ok(limits)
+    }
+
+    /// Non-positive limits would give profiles that never move.
+    fn check(&self) -> Result<()> {
+        check_limit("max_vel", self.max_vel)?;
+        check_limit("max_accel", self.max_accel)?;
+        if let Some(jerk) = self.max_jerk {
+            check_limit("max_jerk", jerk)?;
+        }
+
+        Ok(())
+    }
+
+    /// Caps `max_vel` to what the convertor accepts.
+    fn within(mut self, convertor: &UnitConvertor<f32>) -> Self {
+        if let (_, Some(upper)) = convertor.bounds() {
+            self.max_vel = self.max_vel.min(upper);
+        }
+        self
+    }
+}
+
+fn check_limit(name: &str, v: f32) -> Result<()> {
+    if v > 0.0 && v.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::Other(
+            format!("{} must be positive, got {}", name, v).into(),
+        ))
+    }
+}
+
+/// Online velocity ramp following a changing target, for smoothing teleop
+/// input or any other speed stream.
+#[derive(Debug, Clone)]
+pub struct Ramp {
+    limits: MotionLimits,
+    vel: f32,
+    accel: f32,
+}
+
+impl Ramp {
+    pub fn new(limits: MotionLimits) -> Result<Self> {
+        limits.check()?;
+        Ok(Self {
+            limits,
+            vel: 0.0,
+            accel: 0.0,
+        })
+    }
+
+    pub fn velocity(&self) -> f32 {
+        self.vel
+    }
+
+    pub fn reset(&mut self, vel: f32) {
+        self.vel = vel;
+        self.accel = 0.0;
+    }
+
+    /// Advances by `dt` seconds towards `target`, returning the new velocity.
+    pub fn update(&mut self, target: f32, dt: f32) -> f32 {
+        if dt <= 0.0 {
+            return self.vel;
+        }
+
+        let l = &self.limits;
+        let target = target.clamp(-l.max_vel, l.max_vel);
+        let err = target - self.vel;
+
+        match l.max_jerk {
+            None => {
+                let step = l.max_accel * dt;
+                self.vel += err.clamp(-step, step);
+            }
+
+            Some(jerk) => {
+                let jd = jerk * dt;
+                let sign = if err < 0.0 { -1.0 } else { 1.0 };
+                let (err, accel) = (err.abs(), self.accel * sign);
+
+                // land when the rest fits in one step within the accel limit,
+                // and whose accel can still drop to zero on the next one
+                let land = err / dt;
+                if land <= jd.min(l.max_accel) && (land - accel).abs() <= jd {
+                    self.vel = target;
+                    self.accel = sign * land;
+                    return self.vel;
+                }
+
+                // the largest reachable accel that still allows ramping down
+                // without overshooting the target
+                let mut lo = (accel - jd).max(-l.max_accel);
+                let mut hi = (accel + jd).min(l.max_accel);
+                let next = if reach(hi, jd, dt) <= err {
+                    hi
+                } else if reach(lo, jd, dt) > err {
+                    lo
+                } else {
+                    for _ in 0..32 {
+                        let mid = 0.5 * (lo + hi);
+                        if reach(mid, jd, dt) <= err {
+                            lo = mid;
+                        } else {
+                            hi = mid;
+                        }
+                    }
+                    lo
+                };
+
+                self.accel = sign * next;
+                self.vel += self.accel * dt;
+            }
+        }
+
+        self.vel
+    }
+}
+
+/// Velocity change of a step at `accel`, followed by ramping the accel back
+/// to zero by `jd` per step.
+fn reach(accel: f32, jd: f32, dt: f32) -> f32 {
+    let a = accel.abs();
+    let n = (a / jd).floor();
+    let ramp_down = n * a - jd * n * (n + 1.0) / 2.0;
+    accel.signum() * (a + ramp_down) * dt
+}
+
+/// Trapezoidal profile for a planned move over `distance`, starting and
+/// ending at rest.
+#[derive(Debug, Clone, Copy)]
+pub struct Trapezoid {
+    distance: f32,
+    vel: f32,
+    accel: f32,
+    t_accel: f32,
+    t_cruise: f32,
+}
+
+impl Trapezoid {
+    pub fn new(distance: f32, max_vel: f32, max_accel: f32) -> Result<Self> {
+        check_limit("max_vel", max_vel)?;
+        check_limit("max_accel", max_accel)?;
+
+        let d = distance.abs();
+        // triangular if max_vel can't be reached
+        let vel = max_vel.min((d * max_accel).sqrt());
+        let t_accel = vel / max_accel;
+        let t_cruise = if vel > 0.0 {
+            (d - vel * t_accel) / vel
+        } else {
+            0.0
+        };
+
+        Ok(Self {
+            distance,
+            vel,
+            accel: max_accel,
+            t_accel,
+            t_cruise: t_cruise.max(0.0),
+        })
+    }
+
+    pub fn duration(&self) -> f32 {
+        2.0 * self.t_accel + self.t_cruise
+    }
+
+    /// Unsigned position at `t`.
+    fn position(&self, t: f32) -> f32 {
+        let (ta, tc, total) = (self.t_accel, self.t_cruise, self.duration());
+        let d = self.distance.abs();
+        if t <= 0.0 {
+            0.0
+        } else if t < ta {
+            0.5 * self.accel * t * t
+        } else if t < ta + tc {
+            0.5 * self.vel * ta + self.vel * (t - ta)
+        } else if t < total {
+            d - 0.5 * self.accel * (total - t).powi(2)
+        } else {
+            d
+        }
+    }
+
+    /// Integral of the unsigned position from 0 to `t`.
+    fn position_integral(&self, t: f32) -> f32 {
+        let (ta, tc, total) = (self.t_accel, self.t_cruise, self.duration());
+        let d = self.distance.abs();
+        let a = self.accel;
+        if t <= 0.0 {
+            return 0.0;
+        }
+
+        let acc_part = |t: f32| a * t.powi(3) / 6.0;
+        if t < ta {
+            return acc_part(t);
+        }
+
+        let p1 = 0.5 * self.vel * ta;
+        let cruise_part = |t: f32| p1 * (t - ta) + 0.5 * self.vel * (t - ta).powi(2);
+        if t < ta + tc {
+            return acc_part(ta) + cruise_part(t);
+        }
+
+        let decel_part = |t: f32| d * (t - ta - tc) + a * ((total - t).powi(3) - ta.powi(3)) / 6.0;
+        if t < total {
+            return acc_part(ta) + cruise_part(ta + tc) + decel_part(t);
+        }
+
+        acc_part(ta) + cruise_part(ta + tc) + decel_part(total) + d * (t - total)
+    }
+
+    /// Signed (position, velocity) at `t` seconds into the move.
+    pub fn sample(&self, t: f32) -> (f32, f32) {
+        let sign = self.distance.signum();
+        let (ta, tc, total) = (self.t_accel, self.t_cruise, self.duration());
+        let vel = if t <= 0.0 || t >= total {
+            0.0
+        } else if t < ta {
+            self.accel * t
+        } else if t < ta + tc {
+            self.vel
+        } else {
+            self.accel * (total - t)
+        };
+
+        (sign * self.position(t), sign * vel)
+    }
+}
+
+/// S-curve profile for a planned move, made by averaging a trapezoid over
+/// `max_accel / max_jerk` seconds. This keeps the peak velocity and
+/// acceleration while ramping the acceleration at `max_jerk`.
+#[derive(Debug, Clone, Copy)]
+pub struct SCurve {
+    trapezoid: Trapezoid,
+    t_jerk: f32,
+}
+
+impl SCurve {
+    pub fn new(distance: f32, limits: MotionLimits) -> Result<Self> {
+        limits.check()?;
+        Ok(Self {
+            trapezoid: Trapezoid::new(distance, limits.max_vel, limits.max_accel)?,
+            t_jerk: limits.max_jerk.map_or(0.0, |jerk| limits.max_accel / jerk),
+        })
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.trapezoid.duration() + self.t_jerk
+    }
+
+    /// Signed (position, velocity) at `t` seconds into the move.
+    pub fn sample(&self, t: f32) -> (f32, f32) {
+        let tj = self.t_jerk;
+        if tj <= 0.0 {
+            return self.trapezoid.sample(t);
+        }
+
+        let trap = &self.trapezoid;
+        let sign = trap.distance.signum();
+        let pos = (trap.position_integral(t) - trap.position_integral(t - tj)) / tj;
+        let vel = (trap.position(t) - trap.position(t - tj)) / tj;
+        (sign * pos, sign * vel)
+    }
+}
+
+/// Ramps chassis speed commands, with speed limits capped to the ranges of
+/// the `CHASSIS_SPD_*` convertors.
+#[derive(Debug, Clone)]
+pub struct ChassisProfiler {
+    x: Ramp,
+    y: Ramp,
+    z: Ramp,
+}
+
+impl ChassisProfiler {
+    /// `xy` applies to both x & y in m/s, `z` in °/s.
+    pub fn new(xy: MotionLimits, z: MotionLimits) -> Result<Self> {
+        Ok(Self {
+            x: Ramp::new(xy.within(&CHASSIS_SPD_X_CONVERTOR))?,
+            y: Ramp::new(xy.within(&CHASSIS_SPD_Y_CONVERTOR))?,
+            z: Ramp::new(z.within(&CHASSIS_SPD_Z_CONVERTOR))?,
+        })
+    }
+
+    pub fn update(&mut self, target: Twist, dt: f32) -> Twist {
+        Twist {
+            x: self.x.update(target.x, dt),
+            y: self.y.update(target.y, dt),
+            z: self.z.update(target.z, dt),
+        }
+    }
+
+    /// Ramps towards `target` and sends the result, to be called every `dt`.
+    pub fn drive(&mut self, chassis: &Chassis, target: Twist, dt: f32) -> Result<Twist> {
+        let cmd = self.update(target, dt);
+        chassis.drive_speed(cmd.x, cmd.y, cmd.z)?;
+        Ok(cmd)
+    }
+}
+
+/// Ramps gimbal speed commands, with speed limits capped to the ranges of
+/// the `GIMBAL_*_SPEED` convertors.
+#[derive(Debug, Clone)]
+pub struct GimbalProfiler {
+    pitch: Ramp,
+    yaw: Ramp,
+}
+
+impl GimbalProfiler {
+    pub fn new(pitch: MotionLimits, yaw: MotionLimits) -> Result<Self> {
+        Ok(Self {
+            pitch: Ramp::new(pitch.within(&GIMBAL_PITCH_SPEED_CONVERTOR))?,
+            yaw: Ramp::new(yaw.within(&GIMBAL_YAW_SPEED_CONVERTOR))?,
+        })
+    }
+
+    /// Returns the (pitch, yaw) speeds in °/s.
+    pub fn update(&mut self, pitch: f32, yaw: f32, dt: f32) -> (f32, f32) {
+        (self.pitch.update(pitch, dt), self.yaw.update(yaw, dt))
+    }
+
+    /// Ramps towards the target speeds and sends the result, to be called
+    /// every `dt`.
+    pub fn drive(&mut self, gimbal: &Gimbal, pitch: f32, yaw: f32, dt: f32) -> Result<(f32, f32)> {
+        let (pitch, yaw) = self.update(pitch, yaw, dt);
+        gimbal.drive_speed(pitch, yaw)?;
+        Ok((pitch, yaw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: f32 = 1e-3;
+
+    fn limits(max_jerk: Option<f32>) -> MotionLimits {
+        MotionLimits::new(1.0, 2.0, max_jerk).unwrap()
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < EPS, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn ramp_respects_accel_and_jerk() {
+        let l = limits(Some(10.0));
+        let dt = 0.01;
+        let mut ramp = Ramp::new(l).unwrap();
+        let (mut vel, mut accel) = (0.0, 0.0);
+        let mut step = |ramp: &mut Ramp, target: f32| {
+            let next = ramp.update(target, dt);
+            let next_accel = (next - vel) / dt;
+            assert!(
+                next_accel.abs() <= l.max_accel + EPS,
+                "accel {}",
+                next_accel
+            );
+            let jerk = (next_accel - accel) / dt;
+            assert!(jerk.abs() <= l.max_jerk.unwrap() + EPS, "jerk {}", jerk);
+            (vel, accel) = (next, next_accel);
+        };
+
+        // change of mind while still accelerating
+        for _ in 0..20 {
+            step(&mut ramp, 1.0);
+        }
+
+        for target in [-0.5f32, 0.8, 0.3, 2.0, -2.0] {
+            let mut steps = 0;
+            while ramp.velocity() != target.clamp(-l.max_vel, l.max_vel) {
+                steps += 1;
+                assert!(steps < 1000, "ramp never reached {}", target);
+                step(&mut ramp, target);
+            }
+        }
+    }
+
+    #[test]
+    fn ramp_lands_within_accel_on_coarse_steps() {
+        // a jerk step far above max_accel must not let the landing exceed it
+        let l = limits(Some(100.0));
+        let dt = 0.1;
+        let mut ramp = Ramp::new(l).unwrap();
+        for target in [0.35f32, 1.0, -0.05, -1.0, 0.0] {
+            let mut steps = 0;
+            while ramp.velocity() != target {
+                steps += 1;
+                assert!(steps < 100, "ramp never reached {}", target);
+                let vel = ramp.velocity();
+                let accel = (ramp.update(target, dt) - vel) / dt;
+                assert!(accel.abs() <= l.max_accel + EPS, "accel {}", accel);
+            }
+        }
+    }
+
+    #[test]
+    fn ramp_without_jerk_limit_lands_on_target() {
+        let mut ramp = Ramp::new(limits(None)).unwrap();
+        for _ in 0..100 {
+            ramp.update(0.7, 0.01);
+        }
+        assert_eq!(ramp.velocity(), 0.7);
+    }
+
+    #[test]
+    fn profiles_end_at_rest_on_distance() {
+        for distance in [3.0, -3.0] {
+            let trap = Trapezoid::new(distance, 1.0, 2.0).unwrap();
+            let (pos, vel) = trap.sample(trap.duration());
+            assert_close(pos, distance);
+            assert_close(vel, 0.0);
+
+            let scurve = SCurve::new(distance, limits(Some(10.0))).unwrap();
+            let (pos, vel) = scurve.sample(scurve.duration());
+            assert_close(pos, distance);
+            assert_close(vel, 0.0);
+        }
+    }
+
+    #[test]
+    fn profiles_are_continuous() {
+        let scurve = SCurve::new(3.0, limits(Some(10.0))).unwrap();
+        let dt = 0.01;
+        let mut t = 0.0;
+        let (mut prev_pos, mut prev_vel) = scurve.sample(0.0);
+        while t < scurve.duration() {
+            t += dt;
+            let (pos, vel) = scurve.sample(t);
+            // f32 noise in the averaged position is amplified by 1 / dt
+            let mean_vel = 0.5 * (vel + prev_vel);
+            assert!(((pos - prev_pos) / dt - mean_vel).abs() < 1e-2, "t {}", t);
+            (prev_pos, prev_vel) = (pos, vel);
+        }
+    }
+
+    #[test]
+    fn triangular_when_max_vel_unreachable() {
+        // reaching 2 m/s at 1 m/s² takes 4 m, more than the distance
+        let trap = Trapezoid::new(0.5, 2.0, 1.0).unwrap();
+        let peak = 0.5f32.sqrt();
+        assert_close(trap.duration(), 2.0 * peak);
+        assert_close(trap.sample(trap.duration() / 2.0).1, peak);
+        assert_close(trap.sample(trap.duration()).0, 0.5);
+
+        let scurve = SCurve::new(0.5, MotionLimits::new(2.0, 1.0, Some(5.0)).unwrap()).unwrap();
+        let (pos, vel) = scurve.sample(scurve.duration());
+        assert_close(pos, 0.5);
+        assert_close(vel, 0.0);
+        assert!(scurve.sample(scurve.duration() / 2.0).1 <= peak + EPS);
+    }
+
+    #[test]
+    fn rejects_non_positive_limits() {
+        assert!(MotionLimits::new(1.0, 0.0, None).is_err());
+        assert!(MotionLimits::new(1.0, 1.0, Some(-1.0)).is_err());
+        assert!(Trapezoid::new(1.0, 1.0, 0.0).is_err());
+
+        let bad = MotionLimits {
+            max_vel: 1.0,
+            max_accel: -2.0,
+            max_jerk: None,
+        };
+        assert!(Ramp::new(bad).is_err());
+        assert!(SCurve::new(1.0, bad).is_err());
+    }
+}
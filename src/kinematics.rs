@@ -0,0 +1,116 @@
+//! Mecanum kinematics for the EP chassis.
+//!
+//! Body twists follow the same convention as `Chassis::drive_speed`: x
+//! forward, y right, both in m/s, and z clockwise in °/s. Wheel speeds follow
+//! `Chassis::drive_wheels`: w1 front right, w2 front left, w3 rear left, w4
+//! rear right, in rpm, positive when the wheel pushes the robot forward.
+
+use std::f32::consts::PI;
+
+use crate::util::unit_convertor::WHEEL_SPD_CONVERTOR;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MecanumGeometry {
+    /// m
+    pub wheel_radius: f32,
+    /// distance from the center to the front axle, m
+    pub half_wheelbase: f32,
+    /// distance from the center to the wheel contact line, m
+    pub half_track: f32,
+}
+
+pub const EP_GEOMETRY: MecanumGeometry = MecanumGeometry {
+    wheel_radius: 0.05,
+    half_wheelbase: 0.1,
+    half_track: 0.1,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Twist {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WheelRpm {
+    pub w1: f32,
+    pub w2: f32,
+    pub w3: f32,
+    pub w4: f32,
+}
+
+impl WheelRpm {
+    fn max_abs(&self) -> f32 {
+        self.w1
+            .abs()
+            .max(self.w2.abs())
+            .max(self.w3.abs())
+            .max(self.w4.abs())
+    }
+
+    /// Scales all wheels down together until none exceeds the range accepted
+    /// by `WHEEL_SPD_CONVERTOR`, so the direction of motion is kept instead of
+    /// clamping each wheel on its own.
+    pub fn saturate(self) -> Self {
+        let limit = match WHEEL_SPD_CONVERTOR.bounds() {
+            (_, Some(upper)) => upper as f32,
+            _ => return self,
+        };
+
+        let max = self.max_abs();
+        if max <= limit {
+            return self;
+        }
+
+        let k = limit / max;
+        Self {
+            w1: self.w1 * k,
+            w2: self.w2 * k,
+            w3: self.w3 * k,
+            w4: self.w4 * k,
+        }
+    }
+
+    /// Rounded values in the argument order of `Chassis::drive_wheels`.
+    pub fn to_i16(&self) -> [i16; 4] {
+        [
+            self.w1.round() as i16,
+            self.w2.round() as i16,
+            self.w3.round() as i16,
+            self.w4.round() as i16,
+        ]
+    }
+}
+
+impl MecanumGeometry {
+    fn lever(&self) -> f32 {
+        self.half_wheelbase + self.half_track
+    }
+
+    /// Body twist to wheel rpm, not saturated.
+    pub fn inverse(&self, twist: Twist) -> WheelRpm {
+        let w = twist.z.to_radians() * self.lever();
+        // linear wheel speed in m/s to rpm
+        let k = 60.0 / (2.0 * PI * self.wheel_radius);
+
+        WheelRpm {
+            w1: (twist.x - twist.y - w) * k,
+            w2: (twist.x + twist.y + w) * k,
+            w3: (twist.x - twist.y + w) * k,
+            w4: (twist.x + twist.y - w) * k,
+        }
+    }
+
+    /// Wheel rpm to body twist.
+    pub fn forward(&self, wheels: WheelRpm) -> Twist {
+        let k = 2.0 * PI * self.wheel_radius / 60.0;
+        let (w1, w2, w3, w4) = (wheels.w1 * k, wheels.w2 * k, wheels.w3 * k, wheels.w4 * k);
+
+        Twist {
+            x: (w1 + w2 + w3 + w4) / 4.0,
+            y: (-w1 + w2 - w3 + w4) / 4.0,
+            z: ((-w1 + w2 + w3 - w4) / (4.0 * self.lever())).to_degrees(),
+        }
+    }
+}
@@ -1,8 +1,15 @@
 pub(crate) mod algo;
+pub mod audio;
 pub mod conn;
+pub mod controller;
+pub mod kinematics;
 pub mod modules;
+pub mod odometry;
 pub mod proto;
 mod res;
+mod robot;
 pub(crate) mod util;
+pub mod video;
 
 pub use res::*;
+pub use robot::Robot;
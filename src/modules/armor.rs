@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use crossbeam_channel::unbounded;
+
+use crate::{
+    conn::{Client, EventRx},
+    proto::{
+        host2byte,
+        v1::{
+            ctrl::{ArmorComp, ArmorHitEvent, IrHitEvent, SetArmorParam},
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Result, Robot,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitKind {
+    Water,
+    Collision,
+    /// raw ids of the receiving device and its ir pin, the plate is not
+    /// known for infrared hits
+    Infrared {
+        recv_dev: u8,
+        recv_ir_pin: u8,
+    },
+    Unknown(u8),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HitEvent {
+    /// `None` for infrared hits, or if the robot reported an index outside
+    /// the six plates
+    pub armor: Option<ArmorComp>,
+    pub kind: HitKind,
+    /// microphone peak of the impact, always 0 for infrared hits
+    pub intensity: u16,
+}
+
+impl From<ArmorHitEvent> for HitEvent {
+    fn from(evt: ArmorHitEvent) -> Self {
+        Self {
+            armor: ArmorComp::from_index(evt.index),
+            kind: match evt.typ {
+                0 => HitKind::Water,
+                1 => HitKind::Collision,
+                other => HitKind::Unknown(other),
+            },
+            intensity: evt.mic_value,
+        }
+    }
+}
+
+impl From<IrHitEvent> for HitEvent {
+    fn from(evt: IrHitEvent) -> Self {
+        Self {
+            armor: None,
+            kind: HitKind::Infrared {
+                recv_dev: evt.recv_dev,
+                recv_ir_pin: evt.recv_ir_pin,
+            },
+            intensity: 0,
+        }
+    }
+}
+
+pub struct Armor {
+    host: u8,
+    client: Arc<Client<V1>>,
+}
+
+impl Armor {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(24, 0),
+            client: robot.client().clone(),
+        }
+    }
+
+    /// `level` ranges in [0, 10], higher is more sensitive.
+    pub fn set_hit_sensitivity(&self, comp: ArmorComp, level: u16) -> Result<()> {
+        let energy = unit_convertor::ARMOR_HIT_SENSITIVITY_CONVERTOR.val2proto(level)?;
+
+        self.client.send_cmd(
+            Some(self.host),
+            SetArmorParam {
+                armor_mask: comp as u8,
+                voice_energy_en: energy,
+                voice_energy_ex: energy,
+                voice_len_max: 50,
+                voice_len_min: 15,
+                voice_len_silence: 5,
+                voice_peak_count: 1,
+                voice_peak_min: 160,
+                voice_peak_ave: 180,
+                voice_peak_final: 200,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /// Water, collision and infrared hits merged into one stream.
+    pub fn subscribe_hits(&self) -> Result<EventRx<HitEvent>> {
+        let (tx, rx) = unbounded();
+        let rx = EventRx::new(rx);
+        self.client
+            .forward_event(tx.clone(), rx.alive(), |evt: ArmorHitEvent| {
+                Some(evt.into())
+            })?;
+        self.client
+            .forward_event(tx, rx.alive(), |evt: IrHitEvent| Some(evt.into()))?;
+        Ok(rx)
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{
+    conn::Client,
+    modules::watchdog::Interlock,
+    proto::{
+        host2byte,
+        v1::{
+            ctrl::{BlasterFire, BlasterLedEffect, BlasterSetLed, FireType},
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Error, Result, Robot,
+};
+
+// keeps continuous firing below the rate that triggers the overheat lockout
+pub const DEFAULT_FIRE_INTERVAL: Duration = Duration::from_millis(100);
+
+pub struct Blaster {
+    host: u8,
+    client: Arc<Client<V1>>,
+    fire_interval: Duration,
+    next_fire: Mutex<Option<Instant>>,
+    interlock: Interlock,
+}
+
+impl Blaster {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(23, 0),
+            client: robot.client().clone(),
+            fire_interval: DEFAULT_FIRE_INTERVAL,
+            next_fire: Mutex::new(None),
+            interlock: robot.interlock().clone(),
+        }
+    }
+
+    pub fn set_fire_interval(&mut self, interval: Duration) {
+        self.fire_interval = interval;
+    }
+
+    /// Refuses to fire while the robot's interlock is engaged, e.g. by a
+    /// tripped `Watchdog`.
+    pub fn fire(&self, typ: FireType, times: u8) -> Result<()> {
+        if self.interlock.is_engaged() {
+            return Err(Error::Other("blaster interlock engaged".into()));
+        }
+
+        let times = unit_convertor::BLASTER_FIRE_TIMES_CONVERTOR.val2proto(times)?;
+
+        let mut next_fire = self
+            .next_fire
+            .lock()
+            .map_err(|_| Error::Other("fire rate lock poisoned".into()))?;
+
+        let now = Instant::now();
+        if let Some(wait) = next_fire.and_then(|next| next.checked_duration_since(now)) {
+            return Err(Error::Other(
+                format!("fire rate limited, retry in {:?}", wait).into(),
+            ));
+        }
+
+        self.client.send_cmd(
+            Some(self.host),
+            BlasterFire {
+                typ: typ as u8,
+                times,
+            },
+            None,
+        )?;
+
+        *next_fire = Some(now + self.fire_interval * times as u32);
+        Ok(())
+    }
+
+    pub fn set_led(&self, brightness: u8, effect: BlasterLedEffect) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            BlasterSetLed {
+                effect: effect as u8,
+                r: brightness,
+                g: brightness,
+                b: brightness,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+}
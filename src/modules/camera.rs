@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::Client,
+    proto::{
+        host2byte,
+        v1::{
+            camera::{
+                ExposureMode, GetZoom, SetEv, SetExposureMode, SetWhiteBalance, SetZoom, TakePhoto,
+                WhiteBalanceType,
+            },
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Error, Result, Robot,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum WhiteBalance {
+    Auto,
+    Manual {
+        temperature: u16, // Unit: K
+        tint: i16,
+    },
+}
+
+pub struct Camera {
+    host: u8,
+    client: Arc<Client<V1>>,
+}
+
+impl Camera {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(1, 0),
+            client: robot.client().clone(),
+        }
+    }
+
+    pub fn take_photo(&self) -> Result<()> {
+        self.client
+            .send_cmd(Some(self.host), TakePhoto::default(), None)?;
+        Ok(())
+    }
+
+    pub fn set_zoom(&self, factor: i16) -> Result<()> {
+        let value = unit_convertor::CAMERA_ZOOM_CONVERTOR.val2proto(factor)?;
+        self.client.send_cmd(
+            Some(self.host),
+            SetZoom {
+                value,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_zoom(&self) -> Result<i16> {
+        let resp = self
+            .client
+            .send_cmd(Some(self.host), GetZoom, None)?
+            .ok_or_else(|| Error::Other("no zoom response".into()))?;
+
+        unit_convertor::CAMERA_ZOOM_CONVERTOR.proto2val(resp.value)
+    }
+
+    pub fn set_white_balance(&self, wb: WhiteBalance) -> Result<()> {
+        let msg = match wb {
+            WhiteBalance::Auto => SetWhiteBalance {
+                typ: WhiteBalanceType::Auto,
+                temp1: 0,
+                temp2: 0,
+                tint: 0,
+            },
+
+            WhiteBalance::Manual { temperature, tint } => {
+                let temp: u16 =
+                    unit_convertor::CAMERA_WHITE_BALANCE_TEMP_CONVERTOR.val2proto(temperature)?;
+                let temp = (temp / 100).to_le_bytes();
+                SetWhiteBalance {
+                    typ: WhiteBalanceType::Manual,
+                    temp1: temp[0],
+                    temp2: temp[1],
+                    tint: unit_convertor::CAMERA_WHITE_BALANCE_TINT_CONVERTOR.val2proto(tint)?,
+                }
+            }
+        };
+
+        self.client.send_cmd(Some(self.host), msg, None)?;
+        Ok(())
+    }
+
+    pub fn set_exposure_mode(&self, mode: ExposureMode) -> Result<()> {
+        self.client
+            .send_cmd(Some(self.host), SetExposureMode { mode }, None)?;
+        Ok(())
+    }
+
+    pub fn set_ev(&self, ev: f32) -> Result<()> {
+        let ev: f32 = unit_convertor::CAMERA_EV_CONVERTOR.val2proto(ev)?;
+        self.client.send_cmd(
+            Some(self.host),
+            SetEv {
+                ev: ev.round() as u8,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,29 +1,150 @@
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{bounded, select, Sender};
+use tracing::debug;
+
 use crate::{
     conn::Client,
-    proto::v1::{
-        ctrl::{
-            ChassisPwmFreq, ChassisPwmPercent, ChassisSpeedMode, ChassisStickOverlay,
-            ChassisStickOverlayMode, SetWheelSpeed,
+    kinematics::{Twist, EP_GEOMETRY},
+    modules::{subscriber::Subscriber, wait_action},
+    proto::{
+        host2byte,
+        v1::{
+            action::ChassisMoveAction,
+            ctrl::{
+                ChassisPwmFreq, ChassisPwmPercent, ChassisSetWorkMode, ChassisSpeedMode,
+                ChassisStickOverlay, ChassisStickOverlayMode, ChassisWorkMode, SetWheelSpeed,
+            },
+            subject::{ChassisAttitude, ChassisPosition, ChassisVelocity},
+            subscribe::SubFreq,
+            V1,
         },
-        V1,
     },
     util::unit_convertor,
-    Result,
+    Result, Robot,
 };
 
+// speed commands are pushed without ack, resending keeps the robot from
+// timing them out during a timed drive
+pub const DRIVE_RESEND_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Default)]
+struct Telemetry {
+    position: Option<ChassisPosition>,
+    attitude: Option<ChassisAttitude>,
+    velocity: Option<ChassisVelocity>,
+}
+
 pub struct Chassis {
     host: u8,
-    client: Client<V1>,
+    client: Arc<Client<V1>>,
+    subscriber: Arc<Subscriber>,
+    telemetry: Arc<Mutex<Telemetry>>,
+    telemetry_done_tx: Option<Sender<()>>,
+    telemetry_join: Option<thread::JoinHandle<()>>,
 }
 
 impl Chassis {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(3, 6),
+            client: robot.client().clone(),
+            subscriber: robot.subscriber().clone(),
+            telemetry: Default::default(),
+            telemetry_done_tx: None,
+            telemetry_join: None,
+        }
+    }
+
+    pub fn set_work_mode(&self, mode: ChassisWorkMode) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            ChassisSetWorkMode { mode: mode as u8 },
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Moves by (x, y) in m and rotates by z in degrees, blocking until the
+    /// move is completed.
+    pub fn move_by(&self, x: f32, y: f32, z: f32, spd_xy: f32, spd_z: f32) -> Result<()> {
+        let mut action = ChassisMoveAction::new(x, y, z, spd_xy, spd_z);
+        wait_action(&self.client, &mut action)
+    }
+
+    /// Subscribes to position, attitude and velocity, keeping the latest
+    /// values for the accessors below. Restarts the subscriptions if already
+    /// started.
+    pub fn start_telemetry(&mut self, freq: SubFreq) -> Result<()> {
+        self.stop_telemetry();
+
+        let position = self.subscriber.subscribe::<ChassisPosition>(freq)?;
+        let attitude = self.subscriber.subscribe::<ChassisAttitude>(freq)?;
+        let velocity = self.subscriber.subscribe::<ChassisVelocity>(freq)?;
+
+        let telemetry = self.telemetry.clone();
+        let (done_tx, done_rx) = bounded::<()>(0);
+        let join = thread::spawn(move || {
+            debug!("chassis telemetry loop start");
+            loop {
+                select! {
+                    recv(position.receiver()) -> v => match v {
+                        Ok(v) => update_telemetry(&telemetry, |t| t.position = Some(v)),
+                        Err(_) => break,
+                    },
+
+                    recv(attitude.receiver()) -> v => match v {
+                        Ok(v) => update_telemetry(&telemetry, |t| t.attitude = Some(v)),
+                        Err(_) => break,
+                    },
+
+                    recv(velocity.receiver()) -> v => match v {
+                        Ok(v) => update_telemetry(&telemetry, |t| t.velocity = Some(v)),
+                        Err(_) => break,
+                    },
+
+                    recv(done_rx) -> _ => break,
+                }
+            }
+            debug!("chassis telemetry loop stop");
+        });
+
+        self.telemetry_done_tx = Some(done_tx);
+        self.telemetry_join = Some(join);
+        Ok(())
+    }
+
+    pub fn stop_telemetry(&mut self) {
+        drop(self.telemetry_done_tx.take());
+        if let Some(join) = self.telemetry_join.take() {
+            let _ = join.join();
+        }
+    }
+
+    pub fn position(&self) -> Option<ChassisPosition> {
+        self.telemetry.lock().ok().and_then(|t| t.position)
+    }
+
+    pub fn attitude(&self) -> Option<ChassisAttitude> {
+        self.telemetry.lock().ok().and_then(|t| t.attitude)
+    }
+
+    pub fn velocity(&self) -> Option<ChassisVelocity> {
+        self.telemetry.lock().ok().and_then(|t| t.velocity)
+    }
+
     pub fn stick_overflow(&self, mode: ChassisStickOverlayMode) -> Result<()> {
         self.client
             .send_cmd(Some(self.host), ChassisStickOverlay { mode }, None)?;
         Ok(())
     }
 
-    // TODO: delayed callback?
+    /// Wheel speeds in rpm, w1 front right, w2 front left, w3 rear left, w4
+    /// rear right. Positive always drives the robot forward, the mirrored left
+    /// motors are taken care of here.
     pub fn drive_wheels(&self, w1: i16, w2: i16, w3: i16, w4: i16) -> Result<()> {
         let w1_spd = unit_convertor::WHEEL_SPD_CONVERTOR.val2proto(w1)?;
         let w2_spd = unit_convertor::WHEEL_SPD_CONVERTOR.val2proto(-w2)?;
@@ -62,6 +183,48 @@ impl Chassis {
         Ok(())
     }
 
+    /// Drives a body twist through the wheels, see `kinematics`.
+    pub fn drive_twist(&self, twist: Twist) -> Result<()> {
+        let [w1, w2, w3, w4] = EP_GEOMETRY.inverse(twist).saturate().to_i16();
+        self.drive_wheels(w1, w2, w3, w4)
+    }
+
+    /// Stops all wheels, whichever way they were driven.
+    pub fn stop(&self) -> Result<()> {
+        self.drive_speed(0.0, 0.0, 0.0)?;
+        self.drive_wheels(0, 0, 0, 0)
+    }
+
+    /// Returns a guard that stops the chassis when dropped, including when
+    /// unwinding from a panic.
+    pub fn drive_guard(&self) -> DriveGuard<'_> {
+        DriveGuard {
+            chassis: self,
+            armed: true,
+        }
+    }
+
+    /// Drives at the given speed for `duration`, then stops.
+    pub fn drive_speed_for(&self, x: f32, y: f32, z: f32, duration: Duration) -> Result<()> {
+        let guard = self.drive_guard();
+        repeat_for(duration, || guard.drive_speed(x, y, z))?;
+        guard.stop()
+    }
+
+    /// Drives the wheels at the given rpm for `duration`, then stops.
+    pub fn drive_wheels_for(
+        &self,
+        w1: i16,
+        w2: i16,
+        w3: i16,
+        w4: i16,
+        duration: Duration,
+    ) -> Result<()> {
+        let guard = self.drive_guard();
+        repeat_for(duration, || guard.drive_wheels(w1, w2, w3, w4))?;
+        guard.stop()
+    }
+
     pub fn set_pwm_value(&self, values: [Option<u16>; 6]) -> Result<()> {
         let mut mask = 0;
         let mut pwms = [0u16; 6];
@@ -94,3 +257,62 @@ impl Chassis {
         Ok(())
     }
 }
+
+impl Drop for Chassis {
+    fn drop(&mut self) {
+        self.stop_telemetry();
+    }
+}
+
+pub struct DriveGuard<'a> {
+    chassis: &'a Chassis,
+    armed: bool,
+}
+
+impl<'a> DriveGuard<'a> {
+    /// Stops the chassis now, reporting the error the drop can't.
+    pub fn stop(mut self) -> Result<()> {
+        self.armed = false;
+        self.chassis.stop()
+    }
+}
+
+impl<'a> Deref for DriveGuard<'a> {
+    type Target = Chassis;
+
+    fn deref(&self) -> &Self::Target {
+        self.chassis
+    }
+}
+
+impl<'a> Drop for DriveGuard<'a> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+
+        if let Err(e) = self.chassis.stop() {
+            debug!("failed to stop chassis: {:?}", e);
+        }
+    }
+}
+
+fn repeat_for(duration: Duration, mut f: impl FnMut() -> Result<()>) -> Result<()> {
+    let deadline = Instant::now() + duration;
+    loop {
+        f()?;
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(());
+        }
+
+        thread::sleep(DRIVE_RESEND_INTERVAL.min(deadline - now));
+    }
+}
+
+fn update_telemetry(telemetry: &Mutex<Telemetry>, f: impl FnOnce(&mut Telemetry)) {
+    if let Ok(mut t) = telemetry.lock() {
+        f(&mut t);
+    }
+}
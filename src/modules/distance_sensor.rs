@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::{
+    modules::subscriber::{Subscriber, Subscription},
+    proto::v1::{subject::TofDistances, subscribe::SubFreq},
+    Result, Robot,
+};
+
+/// The infrared TOF distance sensors.
+///
+/// There are no `enable`/`disable` methods on purpose: the v1 binary protocol
+/// has no command to switch the sensors on or off. The
+/// `ir_distance_sensor measure on/off` switch of the official sdk only exists
+/// in its plaintext protocol, which this crate does not speak. Distances are
+/// pushed as long as a subscription from `subscribe_distance` is alive, and
+/// dropping it stops the push.
+pub struct DistanceSensor {
+    subscriber: Arc<Subscriber>,
+}
+
+impl DistanceSensor {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            subscriber: robot.subscriber().clone(),
+        }
+    }
+
+    pub fn subscribe_distance(&self, freq: SubFreq) -> Result<Subscription<TofDistances>> {
+        self.subscriber.subscribe(freq)
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::Client,
+    modules::wait_action,
+    proto::{
+        host2byte,
+        v1::{
+            action::{GimbalCoordinate, GimbalMoveAction, GimbalRecenterAction},
+            gimbal::{
+                GimbalCtrl, GimbalCtrlSpeed, GimbalSetWorkMode, GimbalWorkMode, GIMBAL_CTRL_RESUME,
+                GIMBAL_CTRL_SUSPEND,
+            },
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Result, Robot,
+};
+
+pub struct Gimbal {
+    host: u8,
+    client: Arc<Client<V1>>,
+}
+
+impl Gimbal {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(4, 0),
+            client: robot.client().clone(),
+        }
+    }
+
+    pub fn set_work_mode(&self, mode: GimbalWorkMode, recenter: bool) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            GimbalSetWorkMode {
+                workmode: mode as u8,
+                recenter: recenter.into(),
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn suspend(&self) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            GimbalCtrl {
+                order_code: GIMBAL_CTRL_SUSPEND,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            GimbalCtrl {
+                order_code: GIMBAL_CTRL_RESUME,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn drive_speed(&self, pitch: f32, yaw: f32) -> Result<()> {
+        let pitch_speed = unit_convertor::GIMBAL_PITCH_SPEED_CONVERTOR.val2proto(pitch)?;
+        let yaw_speed = unit_convertor::GIMBAL_YAW_SPEED_CONVERTOR.val2proto(yaw)?;
+
+        self.client.send_cmd(
+            Some(self.host),
+            GimbalCtrlSpeed {
+                pitch_speed,
+                yaw_speed,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn move_to(
+        &self,
+        pitch: f32,
+        yaw: f32,
+        pitch_speed: u16,
+        yaw_speed: u16,
+        coordinate: GimbalCoordinate,
+    ) -> Result<()> {
+        let pitch = unit_convertor::GIMBAL_PITCH_TARGET_CONVERTOR.val2proto(pitch)?;
+        let yaw = unit_convertor::GIMBAL_YAW_TARGET_CONVERTOR.val2proto(yaw)?;
+
+        let mut action = GimbalMoveAction::new(yaw, pitch, yaw_speed, pitch_speed, coordinate);
+        wait_action(&self.client, &mut action)
+    }
+
+    pub fn move_by(&self, pitch: f32, yaw: f32, pitch_speed: u16, yaw_speed: u16) -> Result<()> {
+        let pitch = unit_convertor::GIMBAL_PITCH_MOVE_CONVERTOR.val2proto(pitch)?;
+        let yaw = unit_convertor::GIMBAL_YAW_MOVE_CONVERTOR.val2proto(yaw)?;
+
+        let mut action =
+            GimbalMoveAction::new(yaw, pitch, yaw_speed, pitch_speed, GimbalCoordinate::CUR);
+        wait_action(&self.client, &mut action)
+    }
+
+    pub fn recenter(&self, pitch_speed: u16, yaw_speed: u16) -> Result<()> {
+        let mut action = recenter_action(pitch_speed, yaw_speed)?;
+        wait_action(&self.client, &mut action)
+    }
+
+    /// Starts a recenter and returns without waiting for it to finish.
+    pub(crate) fn start_recenter(&self, pitch_speed: u16, yaw_speed: u16) -> Result<()> {
+        let action = recenter_action(pitch_speed, yaw_speed)?;
+        self.client.send_action(&action)?;
+        Ok(())
+    }
+}
+
+fn recenter_action(pitch_speed: u16, yaw_speed: u16) -> Result<GimbalRecenterAction> {
+    let pitch_speed =
+        unit_convertor::GIMBAL_PITCH_MOVE_SPEED_SET_CONVERTOR.val2proto(pitch_speed)?;
+    let yaw_speed = unit_convertor::GIMBAL_YAW_MOVE_SPEED_SET_CONVERTOR.val2proto(yaw_speed)?;
+    Ok(GimbalRecenterAction::new(pitch_speed, yaw_speed))
+}
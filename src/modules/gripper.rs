@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{
+    conn::Client,
+    modules::subscriber::{Subscriber, Subscription},
+    proto::{
+        host2byte,
+        v1::{
+            gripper::{GripperCtrl, GripperCtrlType},
+            subject::GripperStatus,
+            subscribe::SubFreq,
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Error, Result, Robot,
+};
+
+pub struct Gripper {
+    host: u8,
+    client: Arc<Client<V1>>,
+    subscriber: Arc<Subscriber>,
+}
+
+impl Gripper {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(3, 6),
+            client: robot.client().clone(),
+            subscriber: robot.subscriber().clone(),
+        }
+    }
+
+    /// `power` ranges in [1, 100].
+    pub fn open(&self, power: f32) -> Result<()> {
+        self.ctrl(GripperCtrlType::Open, power)
+    }
+
+    /// `power` ranges in [1, 100].
+    pub fn close(&self, power: f32) -> Result<()> {
+        self.ctrl(GripperCtrlType::Close, power)
+    }
+
+    pub fn pause(&self) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            GripperCtrl {
+                control: GripperCtrlType::Pause as u8,
+                power: 0,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    fn ctrl(&self, typ: GripperCtrlType, power: f32) -> Result<()> {
+        let power: f32 = unit_convertor::GRIPPER_POWER_CONVERTOR.val2proto(power)?;
+        self.client.send_cmd(
+            Some(self.host),
+            GripperCtrl {
+                control: typ as u8,
+                power: power.round() as u16,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn subscribe_status(&self, freq: SubFreq) -> Result<Subscription<GripperStatus>> {
+        self.subscriber.subscribe(freq)
+    }
+
+    /// Blocks until the gripper reports `status`, e.g. after `close` to make
+    /// sure the object is actually held.
+    pub fn wait_status(&self, status: GripperStatus, timeout: Duration) -> Result<()> {
+        let sub = self.subscribe_status(SubFreq::TenHz)?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remain = deadline.saturating_duration_since(Instant::now());
+            match sub.receiver().recv_timeout(remain) {
+                Ok(s) if s == status => return Ok(()),
+                Ok(_) => {}
+                Err(_) => {
+                    return Err(Error::Other(
+                        format!("gripper not {:?} within {:?}", status, timeout).into(),
+                    ))
+                }
+            }
+        }
+    }
+}
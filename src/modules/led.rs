@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::Client,
+    proto::{
+        host2byte,
+        v1::{
+            ctrl::{LedComp, SetSystemLed},
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Error, Result, Robot,
+};
+
+const LED_CTRL_MODE_SDK: u8 = 7;
+const GIMBAL_LED_SEGMENTS: u8 = 8;
+
+#[derive(Debug, Clone, Copy)]
+pub enum LedEffect {
+    Off,
+    On,
+    Pulse,     // breath with faster timing
+    Flash(u8), // frequency in Hz
+    Breath,
+    Scrolling,
+}
+
+impl LedEffect {
+    // see https://github.com/dji-sdk/RoboMaster-SDK/blob/8f301fd1bd3038f51c403614c52abbf9e9f5103c/src/robomaster/led.py
+    fn apply(&self, msg: &mut SetSystemLed) {
+        let (effect_mode, t1, t2) = match *self {
+            Self::Off => (0, 100, 100),
+            Self::On => (1, 100, 100),
+            Self::Pulse => (2, 500, 500),
+            Self::Flash(freq) => {
+                let half = 500 / unit_convertor::LED_FLASH_FREQ_CONVERTOR.check(freq) as i16;
+                (3, half, half)
+            }
+            Self::Breath => (2, 1000, 1000),
+            Self::Scrolling => {
+                msg.led_mask = 0x0f;
+                (4, 30, 40)
+            }
+        };
+
+        msg.effect_mode = effect_mode;
+        msg.t1 = t1;
+        msg.t2 = t2;
+    }
+}
+
+pub struct Led {
+    host: u8,
+    client: Arc<Client<V1>>,
+}
+
+impl Led {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(24, 0),
+            client: robot.client().clone(),
+        }
+    }
+
+    pub fn set_led(&self, comp: LedComp, rgb: (u8, u8, u8), effect: LedEffect) -> Result<()> {
+        self.send_led(comp, 0xff, rgb, effect)
+    }
+
+    pub fn set_gimbal_led(
+        &self,
+        comp: LedComp,
+        segments: &[u8],
+        rgb: (u8, u8, u8),
+        effect: LedEffect,
+    ) -> Result<()> {
+        if !comp.is_gimbal() {
+            return Err(Error::InvalidData(
+                format!("{:?} is not a gimbal led", comp).into(),
+            ));
+        }
+
+        let mut led_mask = 0;
+        for seg in segments.iter().copied() {
+            if seg >= GIMBAL_LED_SEGMENTS {
+                return Err(Error::InvalidData(
+                    format!("invalid gimbal led segment {}", seg).into(),
+                ));
+            }
+
+            led_mask |= 1 << seg;
+        }
+
+        self.send_led(comp, led_mask, rgb, effect)
+    }
+
+    fn send_led(
+        &self,
+        comp: LedComp,
+        led_mask: i16,
+        (r, g, b): (u8, u8, u8),
+        effect: LedEffect,
+    ) -> Result<()> {
+        let mut msg = SetSystemLed {
+            comp_mask: comp as u32,
+            led_mask,
+            ctrl_mode: LED_CTRL_MODE_SDK,
+            r,
+            g,
+            b,
+            ..Default::default()
+        };
+        effect.apply(&mut msg);
+
+        self.client.send_cmd(Some(self.host), msg, None)?;
+        Ok(())
+    }
+}
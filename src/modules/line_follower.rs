@@ -0,0 +1,248 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{bounded, select, unbounded, Receiver, Sender};
+use tracing::debug;
+
+use crate::{
+    controller::{Pid, PidGains},
+    modules::{
+        chassis::Chassis,
+        vision::{LineInfo, Vision},
+    },
+    proto::v1::vision::{LinePoint, LineType, VisionColor, VisionType},
+    Result, Robot,
+};
+
+/// What to do when the detected line forks or crosses another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Junction {
+    /// keep going straight until a plain line shows up again
+    Pass,
+    /// stop the chassis and end following, picking a branch is up to the
+    /// caller
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LineFollowerConfig {
+    pub color: VisionColor,
+    /// index of the line point steered towards, points run from the
+    /// bottom of the image upwards
+    pub lookahead: usize,
+    /// forward speed on a straight line, in m/s
+    pub cruise_speed: f32,
+    /// lower bound of the forward speed in tight curves, in m/s
+    pub min_speed: f32,
+    /// how strongly curvature slows the chassis down
+    pub curvature_slowdown: f32,
+    /// °/s per normalized lateral offset of the lookahead point
+    pub lateral: PidGains,
+    /// °/s per degree of line heading at the lookahead point
+    pub kp_heading: f32,
+    pub max_z: f32,
+    pub junction: Junction,
+    pub loss_timeout: Duration,
+    /// rotation rate while the line is lost, 0 to stand still
+    pub search_z: f32,
+}
+
+impl Default for LineFollowerConfig {
+    fn default() -> Self {
+        Self {
+            color: VisionColor::Blue,
+            lookahead: 3,
+            cruise_speed: 0.3,
+            min_speed: 0.1,
+            curvature_slowdown: 2.0,
+            lateral: PidGains::p(120.0),
+            kp_heading: 0.8,
+            max_z: 90.0,
+            junction: Junction::Pass,
+            loss_timeout: Duration::from_millis(300),
+            search_z: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEvent {
+    Found,
+    Junction(LineType),
+    Lost,
+}
+
+/// Follows a colored line on the floor by driving the chassis speed from
+/// the line points reported by vision. The line detection is enabled on
+/// start and left enabled.
+pub struct LineFollower {
+    rx: Receiver<LineEvent>,
+    done_tx: Option<Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl LineFollower {
+    pub fn start(robot: &Robot, cfg: LineFollowerConfig) -> Result<Self> {
+        let vision = Vision::new(robot);
+        let chassis = Chassis::new(robot);
+
+        vision.set_line_color(cfg.color)?;
+        vision.enable(&[VisionType::Line])?;
+        let lines = vision.subscribe_lines()?;
+
+        let (tx, rx) = unbounded();
+        let (done_tx, done_rx) = bounded::<()>(0);
+        let join = thread::spawn(move || {
+            debug!("line following loop start");
+            let mut follower = Follower {
+                cfg,
+                chassis,
+                lateral: Pid::new(cfg.lateral),
+                on_line: false,
+                in_junction: false,
+                last_seen: Instant::now(),
+                last_step: Instant::now(),
+                tx,
+            };
+
+            loop {
+                select! {
+                    recv(lines.receiver()) -> res => {
+                        let Ok(line) = res else {
+                            break;
+                        };
+
+                        if !follower.on_line(&line) {
+                            break;
+                        }
+                    }
+
+                    recv(done_rx) -> _ => break,
+
+                    default(cfg.loss_timeout) => {}
+                }
+
+                follower.check_lost();
+            }
+
+            if let Err(e) = follower.chassis.stop() {
+                debug!("failed to stop chassis: {:?}", e);
+            }
+            debug!("line following loop stop");
+        });
+
+        Ok(Self {
+            rx,
+            done_tx: Some(done_tx),
+            join: Some(join),
+        })
+    }
+
+    /// Events of the follower, the channel closes once following ends.
+    pub fn receiver(&self) -> &Receiver<LineEvent> {
+        &self.rx
+    }
+}
+
+impl Drop for LineFollower {
+    fn drop(&mut self) {
+        drop(self.done_tx.take());
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+struct Follower {
+    cfg: LineFollowerConfig,
+    chassis: Chassis,
+    lateral: Pid,
+    on_line: bool,
+    in_junction: bool,
+    last_seen: Instant,
+    last_step: Instant,
+    tx: Sender<LineEvent>,
+}
+
+impl Follower {
+    /// Steers from one line detection, returns false once following
+    /// should end.
+    fn on_line(&mut self, line: &LineInfo) -> bool {
+        let Some(target) = line
+            .points
+            .get(self.cfg.lookahead)
+            .or_else(|| line.points.last())
+            .copied()
+        else {
+            return true;
+        };
+
+        if line.line_type == LineType::None {
+            return true;
+        }
+
+        let now = Instant::now();
+        if !self.on_line {
+            self.on_line = true;
+            self.lateral.reset();
+            let _ = self.tx.send(LineEvent::Found);
+        }
+
+        let junction = matches!(line.line_type, LineType::Fork | LineType::Cross);
+        if junction && !self.in_junction {
+            let _ = self.tx.send(LineEvent::Junction(line.line_type));
+        }
+        self.in_junction = junction;
+
+        let res = match (junction, self.cfg.junction) {
+            (true, Junction::Stop) => {
+                if let Err(e) = self.chassis.stop() {
+                    debug!("failed to stop chassis at junction: {:?}", e);
+                }
+                return false;
+            }
+
+            (true, Junction::Pass) => self.chassis.drive_speed(self.cfg.cruise_speed, 0.0, 0.0),
+
+            (false, _) => {
+                let dt = (now - self.last_step).as_secs_f32();
+                let (x, z) = self.steer(target, dt);
+                self.chassis.drive_speed(x, 0.0, z)
+            }
+        };
+
+        if let Err(e) = res {
+            debug!("failed to drive chassis: {:?}", e);
+        }
+
+        self.last_seen = now;
+        self.last_step = now;
+        true
+    }
+
+    /// Forward speed and clockwise rotation rate towards the target point.
+    fn steer(&mut self, target: LinePoint, dt: f32) -> (f32, f32) {
+        let cfg = &self.cfg;
+        let z = (self.lateral.update(target.x - 0.5, dt) + cfg.kp_heading * target.theta)
+            .clamp(-cfg.max_z, cfg.max_z);
+
+        let x = (cfg.cruise_speed / (1.0 + cfg.curvature_slowdown * target.curvature.abs()))
+            .max(cfg.min_speed.min(cfg.cruise_speed));
+
+        (x, z)
+    }
+
+    fn check_lost(&mut self) {
+        if !self.on_line || self.last_seen.elapsed() < self.cfg.loss_timeout {
+            return;
+        }
+
+        self.on_line = false;
+        self.in_junction = false;
+        let _ = self.tx.send(LineEvent::Lost);
+
+        if let Err(e) = self.chassis.drive_speed(0.0, 0.0, self.cfg.search_z) {
+            debug!("failed to stop chassis on line loss: {:?}", e);
+        }
+    }
+}
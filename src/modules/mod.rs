@@ -1 +1,55 @@
+use crate::{
+    conn::Client,
+    proto::{
+        action::{Action, ActionCommand, Progress, State},
+        v1::{V1ActionResponse, V1ActionStatus, V1Ident, V1},
+        Event,
+    },
+    Error, Result,
+};
+
+pub mod armor;
+pub mod blaster;
+pub mod camera;
 pub mod chassis;
+pub mod distance_sensor;
+pub mod gimbal;
+pub mod gripper;
+pub mod led;
+pub mod line_follower;
+pub mod robotic_arm;
+pub mod sensor_adaptor;
+pub mod servo;
+pub mod subscriber;
+pub mod uart;
+pub mod vision;
+pub mod vision_tracking;
+pub mod watchdog;
+
+pub(crate) fn wait_action<A>(client: &Client<V1>, action: &mut A) -> Result<()>
+where
+    A: Action<Status = V1ActionStatus> + Sync + Send + 'static,
+    A::Cmd: ActionCommand<Ident = V1Ident, Response = V1ActionResponse, Seq = u16>,
+    A::Event: Event<Ident = V1Ident> + Send,
+{
+    let progress_rx = client.send_action(action)?;
+    while let Some(prog) = progress_rx.next() {
+        let state = match &prog {
+            Progress::Response(resp) => State::from(*resp),
+            Progress::Event(status, _) => status.state,
+        };
+
+        if !action.apply_progress(prog)? {
+            continue;
+        }
+
+        return match state {
+            State::Failed | State::Rejected | State::Exception | State::Aborted => Err(
+                Error::Other(format!("action finished with state {:?}", state).into()),
+            ),
+            _ => Ok(()),
+        };
+    }
+
+    Err(Error::Other("action progress chan broken".into()))
+}
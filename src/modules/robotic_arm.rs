@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::Client,
+    modules::{
+        subscriber::{Subscriber, Subscription},
+        wait_action,
+    },
+    proto::{
+        host2byte,
+        v1::{
+            action::{RoboticArmMoveAction, RoboticArmMoveMode},
+            gripper::RoboticArmGetPostion,
+            subject::ArmPosition,
+            subscribe::SubFreq,
+            V1,
+        },
+    },
+    Error, Result, Robot,
+};
+
+pub struct RoboticArm {
+    host: u8,
+    client: Arc<Client<V1>>,
+    subscriber: Arc<Subscriber>,
+}
+
+impl RoboticArm {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(3, 6),
+            client: robot.client().clone(),
+            subscriber: robot.subscriber().clone(),
+        }
+    }
+
+    /// Moves the end point to (x, y) in mm, relative to the arm base.
+    pub fn move_to(&self, x: i32, y: i32) -> Result<()> {
+        let mut action = RoboticArmMoveAction::new(x, y, 0, RoboticArmMoveMode::Absolutely);
+        wait_action(&self.client, &mut action)
+    }
+
+    /// Moves the end point by (x, y) in mm from where it is now.
+    pub fn move_by(&self, x: i32, y: i32) -> Result<()> {
+        let mut action = RoboticArmMoveAction::new(x, y, 0, RoboticArmMoveMode::Relatively);
+        wait_action(&self.client, &mut action)
+    }
+
+    /// Moves the end point back to the origin of the arm frame. There is no
+    /// dedicated recenter command in this protocol set, so this is an
+    /// absolute move to (0, 0) and ends wherever the firmware puts that target.
+    pub fn recenter(&self) -> Result<()> {
+        self.move_to(0, 0)
+    }
+
+    pub fn get_position(&self) -> Result<ArmPosition> {
+        let resp = self
+            .client
+            .send_cmd(Some(self.host), RoboticArmGetPostion::default(), None)?
+            .ok_or_else(|| Error::Other("no arm position response".into()))?;
+
+        Ok(ArmPosition {
+            x: resp.x,
+            y: resp.y,
+        })
+    }
+
+    pub fn subscribe_position(&self, freq: SubFreq) -> Result<Subscription<ArmPosition>> {
+        self.subscriber.subscribe(freq)
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{bounded, select, unbounded, Receiver, Sender, TryRecvError};
+use tracing::debug;
+
+use crate::{
+    conn::Client,
+    proto::{
+        host2byte,
+        v1::{
+            ctrl::{SensorGetData, SensorGetDataResp},
+            V1,
+        },
+    },
+    Error, Result, Robot,
+};
+
+// a sensor adaptor that stops answering must not block its caller, or the
+// poll thread which is joined on drop
+const SENSOR_RESP_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy)]
+pub enum SensorWatch {
+    /// fires when the adc value crosses the threshold in either direction
+    AdcThreshold(u16),
+    /// fires on every level change of the io pin
+    IoEdge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Rising,
+    Falling,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SensorEvent {
+    pub id: u8,
+    pub port: u8,
+    pub edge: Edge,
+    pub adc: u16,
+    pub io: u8,
+}
+
+pub struct SensorAdaptor {
+    client: Arc<Client<V1>>,
+}
+
+impl SensorAdaptor {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            client: robot.client().clone(),
+        }
+    }
+
+    pub fn get_adc(&self, id: u8, port: u8) -> Result<u16> {
+        get_data(&self.client, id, port).map(|resp| resp.adc)
+    }
+
+    pub fn get_io(&self, id: u8, port: u8) -> Result<u8> {
+        get_data(&self.client, id, port).map(|resp| resp.io)
+    }
+
+    /// Period of the last pulse seen on the io pin, in ms.
+    pub fn get_pulse_period(&self, id: u8, port: u8) -> Result<u32> {
+        get_data(&self.client, id, port).map(|resp| resp.time)
+    }
+
+    /// Starts a background poller sampling each `(id, port, watch)` every
+    /// `interval`. The first sample of each entry only sets the baseline.
+    pub fn poll(&self, watches: Vec<(u8, u8, SensorWatch)>, interval: Duration) -> SensorPoller {
+        let client = self.client.clone();
+        let (tx, rx) = unbounded();
+        let (done_tx, done_rx) = bounded(0);
+        let join = thread::spawn(move || {
+            debug!("sensor poll loop start");
+            start_sensor_poll(client, watches, interval, tx, done_rx);
+            debug!("sensor poll loop stop");
+        });
+
+        SensorPoller {
+            rx,
+            done_tx: Some(done_tx),
+            join: Some(join),
+        }
+    }
+}
+
+pub struct SensorPoller {
+    rx: Receiver<SensorEvent>,
+    done_tx: Option<Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl SensorPoller {
+    pub fn receiver(&self) -> &Receiver<SensorEvent> {
+        &self.rx
+    }
+}
+
+impl Iterator for SensorPoller {
+    type Item = SensorEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rx.recv().ok()
+    }
+}
+
+impl Drop for SensorPoller {
+    fn drop(&mut self) {
+        drop(self.done_tx.take());
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+fn get_data(client: &Client<V1>, id: u8, port: u8) -> Result<SensorGetDataResp> {
+    client
+        .send_cmd_timeout(
+            Some(host2byte(22, id)),
+            SensorGetData { port },
+            None,
+            SENSOR_RESP_TIMEOUT,
+        )?
+        .ok_or_else(|| Error::Other("no sensor data response".into()))
+}
+
+fn start_sensor_poll(
+    client: Arc<Client<V1>>,
+    watches: Vec<(u8, u8, SensorWatch)>,
+    interval: Duration,
+    tx: Sender<SensorEvent>,
+    done: Receiver<()>,
+) {
+    let mut levels: Vec<Option<bool>> = vec![None; watches.len()];
+    loop {
+        for ((id, port, watch), level) in watches.iter().zip(levels.iter_mut()) {
+            // each poll may take up to the response timeout, don't wait for
+            // the whole round once stopped
+            if !matches!(done.try_recv(), Err(TryRecvError::Empty)) {
+                return;
+            }
+
+            let resp = match get_data(&client, *id, *port) {
+                Ok(resp) => resp,
+                Err(e) => {
+                    debug!(id, port, "failed to poll sensor: {:?}", e);
+                    continue;
+                }
+            };
+
+            let high = match watch {
+                SensorWatch::AdcThreshold(threshold) => resp.adc >= *threshold,
+                SensorWatch::IoEdge => resp.io != 0,
+            };
+
+            let prev = level.replace(high);
+            if prev.is_none() || prev == Some(high) {
+                continue;
+            }
+
+            let evt = SensorEvent {
+                id: *id,
+                port: *port,
+                edge: if high { Edge::Rising } else { Edge::Falling },
+                adc: resp.adc,
+                io: resp.io,
+            };
+
+            if tx.send(evt).is_err() {
+                return;
+            }
+        }
+
+        select! {
+            recv(done) -> _ => {
+                return;
+            }
+
+            default(interval) => {}
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::Client,
+    modules::{
+        subscriber::{Subscriber, Subscription},
+        wait_action,
+    },
+    proto::{
+        host2byte,
+        v1::{
+            action::{ServoIndex, ServoSetAngleAction},
+            gripper::{ServoControl, ServoGetAngle, ServoMode, ServoModeSet},
+            subject::ServoStates,
+            subscribe::SubFreq,
+            V1,
+        },
+    },
+    util::unit_convertor,
+    Error, Result, Robot,
+};
+
+pub struct Servo {
+    host: u8,
+    client: Arc<Client<V1>>,
+    subscriber: Arc<Subscriber>,
+}
+
+impl Servo {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(3, 6),
+            client: robot.client().clone(),
+            subscriber: robot.subscriber().clone(),
+        }
+    }
+
+    pub fn set_mode(&self, index: ServoIndex, mode: ServoMode) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            ServoModeSet {
+                id: host2byte(25, index as u8),
+                mode: mode as u8,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /// `speed` ranges in [-49, 49], only effective in `ServoMode::Speed`.
+    pub fn drive_speed(&self, index: ServoIndex, speed: f32) -> Result<()> {
+        let value: f32 = unit_convertor::SERVO_SPEED_CONVERTOR.val2proto(speed)?;
+        self.client.send_cmd(
+            Some(self.host),
+            ServoControl {
+                id: host2byte(25, index as u8),
+                enabled: true,
+                value: value.round() as u16,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn stop(&self, index: ServoIndex) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            ServoControl {
+                id: host2byte(25, index as u8),
+                enabled: false,
+                value: 0,
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn move_to(&self, index: ServoIndex, angle: i32) -> Result<()> {
+        let mut action = ServoSetAngleAction::new(index, angle);
+        wait_action(&self.client, &mut action)
+    }
+
+    pub fn get_angle(&self, index: ServoIndex) -> Result<i32> {
+        self.client
+            .send_cmd(
+                Some(self.host),
+                ServoGetAngle {
+                    id: host2byte(25, index as u8),
+                },
+                None,
+            )?
+            .map(|resp| resp.angle)
+            .ok_or_else(|| Error::Other("no servo angle response".into()))
+    }
+
+    pub fn subscribe_states(&self, freq: SubFreq) -> Result<Subscription<ServoStates>> {
+        self.subscriber.subscribe(freq)
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    Arc, Mutex,
+};
+
+use crossbeam_channel::Receiver;
+use tracing::debug;
+
+use crate::{
+    conn::{Client, EventRx},
+    proto::{
+        host2byte,
+        v1::{
+            subscribe::{
+                AddSubMsg, DelMsg, PushPeriodMsg, SubFreq, SubNodeReset, Subject, SubscribeAddNode,
+            },
+            V1,
+        },
+    },
+    Error, Result,
+};
+
+/// Manages periodic data subscriptions for a robot, shared by all modules.
+pub struct Subscriber {
+    host: u8,
+    client: Arc<Client<V1>>,
+    node_added: Mutex<bool>,
+    next_msg_id: AtomicU8,
+}
+
+impl Subscriber {
+    pub(crate) fn new(client: Arc<Client<V1>>) -> Self {
+        Self {
+            host: host2byte(9, 0),
+            client,
+            node_added: Mutex::new(false),
+            next_msg_id: AtomicU8::new(1),
+        }
+    }
+
+    pub fn subscribe<S: Subject>(&self, freq: SubFreq) -> Result<Subscription<S>> {
+        self.ensure_node()?;
+
+        let node_id = self.client.host();
+        let msg_id = self.next_msg_id();
+
+        let rx = self.client.subscribe_event(move |msg: PushPeriodMsg| {
+            if msg.msg_id != msg_id {
+                return None;
+            }
+
+            S::de(&msg.data[..])
+                .map_err(|e| debug!(msg_id, uid = S::UID, "invalid subject data: {:?}", e))
+                .ok()
+        })?;
+
+        self.client.send_cmd(
+            Some(self.host),
+            AddSubMsg {
+                node_id,
+                msg_id,
+                sub_uid_list: vec![S::UID],
+                sub_freq: freq as u16,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(Subscription {
+            host: self.host,
+            client: self.client.clone(),
+            node_id,
+            msg_id,
+            rx,
+        })
+    }
+
+    fn ensure_node(&self) -> Result<()> {
+        let mut added = self
+            .node_added
+            .lock()
+            .map_err(|_| Error::Other("subscriber lock poisoned".into()))?;
+
+        if *added {
+            return Ok(());
+        }
+
+        let node_id = self.client.host();
+        self.client
+            .send_cmd(Some(self.host), SubNodeReset { node_id }, None)?;
+        self.client.send_cmd(
+            Some(self.host),
+            SubscribeAddNode {
+                node_id,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        *added = true;
+        Ok(())
+    }
+
+    fn next_msg_id(&self) -> u8 {
+        loop {
+            let id = self.next_msg_id.fetch_add(1, Ordering::Relaxed);
+            // msg id 0 is never used by the robot
+            if id != 0 {
+                return id;
+            }
+        }
+    }
+}
+
+/// A live subscription, removed from the robot when dropped.
+pub struct Subscription<S: Subject> {
+    host: u8,
+    client: Arc<Client<V1>>,
+    node_id: u8,
+    msg_id: u8,
+    rx: EventRx<S>,
+}
+
+impl<S: Subject> Subscription<S> {
+    pub fn next(&self) -> Option<S> {
+        self.rx.next()
+    }
+
+    pub fn receiver(&self) -> &Receiver<S> {
+        self.rx.receiver()
+    }
+}
+
+impl<S: Subject> Drop for Subscription<S> {
+    fn drop(&mut self) {
+        if let Err(e) = self.client.send_cmd(
+            Some(self.host),
+            DelMsg {
+                sub_mode: 0,
+                node_id: self.node_id,
+                msg_id: self.msg_id,
+            },
+            None,
+        ) {
+            debug!(
+                msg_id = self.msg_id,
+                "failed to delete subscription: {:?}", e
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::{
+    conn::{Client, EventRx},
+    proto::{
+        host2byte,
+        v1::{
+            ctrl::{
+                ChassisSerialMsgRecv, ChassisSerialMsgSend, ChassisSerialSet, SerialBaudRate,
+                SerialDataBit, SerialOddEven, SerialStopBit,
+            },
+            V1,
+        },
+    },
+    Error, Result, Robot,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct UartConfig {
+    pub baud_rate: SerialBaudRate,
+    pub data_bit: SerialDataBit,
+    pub parity: SerialOddEven,
+    pub stop_bit: SerialStopBit,
+    pub tx_size: u16,
+    pub rx_size: u16,
+}
+
+impl Default for UartConfig {
+    fn default() -> Self {
+        Self {
+            baud_rate: SerialBaudRate::Rate9600,
+            data_bit: SerialDataBit::Bit8,
+            parity: SerialOddEven::None,
+            stop_bit: SerialStopBit::One,
+            tx_size: 50,
+            rx_size: 50,
+        }
+    }
+}
+
+pub struct Uart {
+    host: u8,
+    client: Arc<Client<V1>>,
+}
+
+impl Uart {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(3, 6),
+            client: robot.client().clone(),
+        }
+    }
+
+    pub fn configure(&self, cfg: &UartConfig) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            ChassisSerialSet {
+                baud_rate: cfg.baud_rate,
+                data_bit: cfg.data_bit,
+                odd_even: cfg.parity,
+                stop_bit: cfg.stop_bit,
+                rx_size: cfg.rx_size,
+                tx_size: cfg.tx_size,
+                ..Default::default()
+            },
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn send(&self, data: &[u8]) -> Result<()> {
+        self.client.send_cmd(
+            Some(self.host),
+            ChassisSerialMsgSend::new(data.to_owned()),
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /// Each item is one chunk of bytes as forwarded by the robot, chunk
+    /// boundaries carry no meaning.
+    pub fn subscribe(&self) -> Result<EventRx<Vec<u8>>> {
+        self.client
+            .subscribe_event(|msg: ChassisSerialMsgRecv| Some(msg.msg))
+    }
+}
+
+impl io::Write for Uart {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.send(buf) {
+            Ok(_) => Ok(buf.len()),
+            Err(Error::IO(e)) => Err(e),
+            Err(e) => Err(io::Error::new(io::ErrorKind::Other, format!("{:?}", e))),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::{Client, EventRx},
+    proto::{
+        host2byte,
+        v1::{
+            vision::{
+                Gesture, LinePoint, LineType, Marker, VisionColor, VisionColorType,
+                VisionDetectEnable, VisionDetectInfo, VisionDetectStatus, VisionRectInfo,
+                VisionSetColor, VisionType, VisionTypeMask,
+            },
+            V1,
+        },
+    },
+    Error, Result, Robot,
+};
+
+// x & y are the normalized center of the box, w & h the normalized size
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl From<[f32; 4]> for Rect {
+    fn from(v: [f32; 4]) -> Self {
+        Self {
+            x: v[0],
+            y: v[1],
+            w: v[2],
+            h: v[3],
+        }
+    }
+}
+
+pub type ShoulderBox = Rect;
+pub type PersonBox = Rect;
+pub type RobotBox = Rect;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GestureInfo {
+    pub rect: Rect,
+    pub gesture: Gesture,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MarkerInfo {
+    pub rect: Rect,
+    pub marker: Marker,
+}
+
+#[derive(Debug, Clone)]
+pub struct LineInfo {
+    pub line_type: LineType,
+    pub points: Vec<LinePoint>,
+}
+
+pub struct Vision {
+    host: u8,
+    client: Arc<Client<V1>>,
+}
+
+impl Vision {
+    pub fn new(robot: &Robot) -> Self {
+        Self {
+            host: host2byte(17, 7),
+            client: robot.client().clone(),
+        }
+    }
+
+    pub fn enable(&self, types: &[VisionType]) -> Result<()> {
+        let mask = types
+            .iter()
+            .fold(VisionTypeMask::default(), |mask, typ| mask.set(*typ));
+
+        self.client
+            .send_cmd(Some(self.host), VisionDetectEnable(mask), None)?;
+        Ok(())
+    }
+
+    pub fn disable_all(&self) -> Result<()> {
+        self.enable(&[])
+    }
+
+    pub fn detect_status(&self) -> Result<VisionTypeMask> {
+        self.client
+            .send_cmd(Some(self.host), VisionDetectStatus, None)?
+            .ok_or_else(|| Error::Other("no detect status response".into()))
+    }
+
+    pub fn set_line_color(&self, color: VisionColor) -> Result<()> {
+        self.set_color(VisionColorType::Line, color)
+    }
+
+    pub fn set_marker_color(&self, color: VisionColor) -> Result<()> {
+        self.set_color(VisionColorType::Marker, color)
+    }
+
+    fn set_color(&self, typ: VisionColorType, color: VisionColor) -> Result<()> {
+        self.client
+            .send_cmd(Some(self.host), VisionSetColor { typ, color }, None)?;
+        Ok(())
+    }
+
+    pub fn subscribe_shoulders(&self) -> Result<EventRx<Vec<ShoulderBox>>> {
+        self.subscribe(|info| match info {
+            VisionRectInfo::Shoulder(rects) => Some(rects.into_iter().map(From::from).collect()),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_persons(&self) -> Result<EventRx<Vec<PersonBox>>> {
+        self.subscribe(|info| match info {
+            VisionRectInfo::Person(rects) => Some(rects.into_iter().map(From::from).collect()),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_robots(&self) -> Result<EventRx<Vec<RobotBox>>> {
+        self.subscribe(|info| match info {
+            VisionRectInfo::Robot(rects) => Some(rects.into_iter().map(From::from).collect()),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_gestures(&self) -> Result<EventRx<Vec<GestureInfo>>> {
+        self.subscribe(|info| match info {
+            VisionRectInfo::Gesture(rects) => Some(
+                rects
+                    .into_iter()
+                    .map(|(rect, gesture)| GestureInfo {
+                        rect: rect.into(),
+                        gesture,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_markers(&self) -> Result<EventRx<Vec<MarkerInfo>>> {
+        self.subscribe(|info| match info {
+            VisionRectInfo::Marker(rects) => Some(
+                rects
+                    .into_iter()
+                    .map(|(rect, marker)| MarkerInfo {
+                        rect: rect.into(),
+                        marker,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_lines(&self) -> Result<EventRx<LineInfo>> {
+        self.subscribe(|info| match info {
+            VisionRectInfo::Line(line_type, points) => Some(LineInfo { line_type, points }),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn subscribe<T, F>(&self, map: F) -> Result<EventRx<T>>
+    where
+        T: Send + 'static,
+        F: Fn(VisionRectInfo) -> Option<T> + Send + Sync + 'static,
+    {
+        self.client
+            .subscribe_event(move |info: VisionDetectInfo| map(info.rect_info))
+    }
+}
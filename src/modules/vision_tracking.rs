@@ -0,0 +1,248 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{bounded, select, unbounded, Receiver, Sender};
+use tracing::debug;
+
+use crate::{
+    controller::{Pid, PidGains},
+    modules::{
+        gimbal::Gimbal,
+        vision::{Rect, Vision},
+    },
+    proto::v1::vision::{Marker, VisionRectInfo, VisionType},
+    Result, Robot,
+};
+
+// how often the reacquire strategy is applied while no target is tracked
+const REACQUIRE_TICK: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackTarget {
+    Person,
+    Robot,
+    /// any marker if `None`
+    Marker(Option<Marker>),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Reacquire {
+    /// stop where the target was lost
+    Hold,
+    /// return to center, at the given speed in °/s. The recenter is not
+    /// waited for, a target found on the way takes over the gimbal
+    Recenter { speed: u16 },
+    /// keep turning at the given yaw speed in °/s until a target shows up
+    Scan { yaw_speed: f32 },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TrackerConfig {
+    pub target: TrackTarget,
+    /// °/s per normalized horizontal offset
+    pub yaw: PidGains,
+    /// °/s per normalized vertical offset
+    pub pitch: PidGains,
+    /// normalized offset from the image center treated as centred
+    pub deadband: f32,
+    pub loss_timeout: Duration,
+    pub reacquire: Reacquire,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        Self {
+            target: TrackTarget::Person,
+            yaw: PidGains::p(120.0),
+            pitch: PidGains::p(80.0),
+            deadband: 0.02,
+            loss_timeout: Duration::from_millis(500),
+            reacquire: Reacquire::Hold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TrackEvent {
+    Acquired(Rect),
+    Lost,
+}
+
+/// Keeps a detected target centred by driving the gimbal speed. The vision
+/// detection for the target is enabled on start and left enabled.
+pub struct GimbalTracker {
+    rx: Receiver<TrackEvent>,
+    done_tx: Option<Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl GimbalTracker {
+    pub fn start(robot: &Robot, cfg: TrackerConfig) -> Result<Self> {
+        let vision = Vision::new(robot);
+        let gimbal = Gimbal::new(robot);
+
+        vision.enable(&[match cfg.target {
+            TrackTarget::Person => VisionType::Person,
+            TrackTarget::Robot => VisionType::Robot,
+            TrackTarget::Marker(_) => VisionType::Marker,
+        }])?;
+
+        let target = cfg.target;
+        let rects = vision.subscribe(move |info| match (target, info) {
+            (TrackTarget::Person, VisionRectInfo::Person(rects))
+            | (TrackTarget::Robot, VisionRectInfo::Robot(rects)) => {
+                Some(rects.into_iter().map(Rect::from).collect::<Vec<_>>())
+            }
+
+            (TrackTarget::Marker(want), VisionRectInfo::Marker(markers)) => Some(
+                markers
+                    .into_iter()
+                    .filter(|(_, marker)| want.map_or(true, |w| w == *marker))
+                    .map(|(rect, _)| Rect::from(rect))
+                    .collect(),
+            ),
+
+            _ => None,
+        })?;
+
+        let (tx, rx) = unbounded();
+        let (done_tx, done_rx) = bounded::<()>(0);
+        let join = thread::spawn(move || {
+            debug!("gimbal tracking loop start");
+            let mut tracker = Tracker {
+                cfg,
+                gimbal,
+                yaw: Pid::new(cfg.yaw),
+                pitch: Pid::new(cfg.pitch),
+                tracked: None,
+                last_seen: Instant::now(),
+                last_step: Instant::now(),
+                tx,
+            };
+
+            loop {
+                let timeout = if tracker.tracked.is_some() {
+                    cfg.loss_timeout.saturating_sub(tracker.last_seen.elapsed())
+                } else {
+                    REACQUIRE_TICK
+                };
+
+                select! {
+                    recv(rects.receiver()) -> res => {
+                        let Ok(rects) = res else {
+                            break;
+                        };
+
+                        tracker.on_detection(&rects);
+                    }
+
+                    recv(done_rx) -> _ => break,
+
+                    default(timeout) => {}
+                }
+
+                tracker.check_lost();
+            }
+
+            if let Err(e) = tracker.gimbal.drive_speed(0.0, 0.0) {
+                debug!("failed to stop gimbal: {:?}", e);
+            }
+            debug!("gimbal tracking loop stop");
+        });
+
+        Ok(Self {
+            rx,
+            done_tx: Some(done_tx),
+            join: Some(join),
+        })
+    }
+
+    pub fn receiver(&self) -> &Receiver<TrackEvent> {
+        &self.rx
+    }
+}
+
+impl Drop for GimbalTracker {
+    fn drop(&mut self) {
+        drop(self.done_tx.take());
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
+
+struct Tracker {
+    cfg: TrackerConfig,
+    gimbal: Gimbal,
+    yaw: Pid,
+    pitch: Pid,
+    tracked: Option<Rect>,
+    last_seen: Instant,
+    last_step: Instant,
+    tx: Sender<TrackEvent>,
+}
+
+impl Tracker {
+    /// The detection closest to the tracked target, or to the image center
+    /// when nothing is tracked yet.
+    fn pick(&self, rects: &[Rect]) -> Option<Rect> {
+        let (cx, cy) = self.tracked.map_or((0.5, 0.5), |r| (r.x, r.y));
+        rects.iter().copied().min_by(|a, b| {
+            let da = (a.x - cx).powi(2) + (a.y - cy).powi(2);
+            let db = (b.x - cx).powi(2) + (b.y - cy).powi(2);
+            da.total_cmp(&db)
+        })
+    }
+
+    fn on_detection(&mut self, rects: &[Rect]) {
+        let Some(rect) = self.pick(rects) else {
+            return;
+        };
+
+        let now = Instant::now();
+        if self.tracked.is_none() {
+            self.yaw.reset();
+            self.pitch.reset();
+            let _ = self.tx.send(TrackEvent::Acquired(rect));
+        } else {
+            let dt = (now - self.last_step).as_secs_f32();
+            let deadband = |e: f32| if e.abs() < self.cfg.deadband { 0.0 } else { e };
+            let yaw = self.yaw.update(deadband(rect.x - 0.5), dt);
+            // image y grows downwards, gimbal pitch upwards
+            let pitch = -self.pitch.update(deadband(rect.y - 0.5), dt);
+            if let Err(e) = self.gimbal.drive_speed(pitch, yaw) {
+                debug!("failed to drive gimbal: {:?}", e);
+            }
+        }
+
+        self.tracked = Some(rect);
+        self.last_seen = now;
+        self.last_step = now;
+    }
+
+    fn check_lost(&mut self) {
+        if self.tracked.is_some() {
+            if self.last_seen.elapsed() < self.cfg.loss_timeout {
+                return;
+            }
+
+            self.tracked = None;
+            let _ = self.tx.send(TrackEvent::Lost);
+
+            let res = match self.cfg.reacquire {
+                Reacquire::Hold | Reacquire::Scan { .. } => self.gimbal.drive_speed(0.0, 0.0),
+                Reacquire::Recenter { speed } => self.gimbal.start_recenter(speed, speed),
+            };
+
+            if let Err(e) = res {
+                debug!("failed to stop gimbal on target loss: {:?}", e);
+            }
+        }
+
+        if let Reacquire::Scan { yaw_speed } = self.cfg.reacquire {
+            if let Err(e) = self.gimbal.drive_speed(0.0, yaw_speed) {
+                debug!("failed to scan for target: {:?}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{after, bounded, select, unbounded, Receiver, Sender};
+use tracing::debug;
+
+use crate::{
+    modules::{chassis::Chassis, gimbal::Gimbal},
+    Robot,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum WatchdogEvent {
+    /// not fed for `starved`, motion has been stopped
+    Tripped { starved: Duration },
+    /// fed again after a trip
+    Recovered,
+}
+
+/// Robot wide flag engaged while any watchdog is tripped, used to refuse
+/// commands that can't simply be zeroed. Every `Blaster` of the robot checks
+/// it before firing.
+#[derive(Debug, Clone, Default)]
+pub struct Interlock(Arc<AtomicUsize>);
+
+impl Interlock {
+    pub fn is_engaged(&self) -> bool {
+        self.0.load(Ordering::Acquire) > 0
+    }
+
+    // each engage must be paired with exactly one release by the same owner
+    pub(crate) fn engage(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+
+    pub(crate) fn release(&self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Client side deadman switch for motion. If `feed` is not called within the
+/// timeout, the chassis and gimbal speed are zeroed and the robot's
+/// interlock is engaged until the next feed, or until the watchdog is
+/// dropped. While tripped the stop is sent again every timeout, so motion
+/// commanded in between doesn't outlive the next period.
+pub struct Watchdog {
+    feed_tx: Sender<()>,
+    evt_rx: Receiver<WatchdogEvent>,
+    interlock: Interlock,
+    done_tx: Option<Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl Watchdog {
+    pub fn start(robot: &Robot, timeout: Duration) -> Self {
+        let chassis = Chassis::new(robot);
+        let gimbal = Gimbal::new(robot);
+        let interlock = robot.interlock().clone();
+
+        let (feed_tx, feed_rx) = bounded(1);
+        let (evt_tx, evt_rx) = unbounded();
+        let (done_tx, done_rx) = bounded(0);
+        let loop_interlock = interlock.clone();
+        let join = thread::spawn(move || {
+            debug!(?timeout, "watchdog loop start");
+            let mut last_feed = Instant::now();
+            let mut tripped = false;
+            loop {
+                let timer = if tripped {
+                    after(timeout)
+                } else {
+                    after(timeout.saturating_sub(last_feed.elapsed()))
+                };
+
+                select! {
+                    recv(feed_rx) -> res => {
+                        if res.is_err() {
+                            break;
+                        }
+
+                        last_feed = Instant::now();
+                        if tripped {
+                            tripped = false;
+                            loop_interlock.release();
+                            let _ = evt_tx.send(WatchdogEvent::Recovered);
+                        }
+                    }
+
+                    recv(timer) -> _ => {
+                        let starved = last_feed.elapsed();
+                        let first_trip = !tripped;
+                        if first_trip {
+                            debug!(?starved, "watchdog tripped");
+                            tripped = true;
+                            loop_interlock.engage();
+                        }
+
+                        if let Err(e) = chassis.stop() {
+                            debug!("watchdog failed to stop chassis: {:?}", e);
+                        }
+
+                        if let Err(e) = gimbal.drive_speed(0.0, 0.0) {
+                            debug!("watchdog failed to stop gimbal: {:?}", e);
+                        }
+
+                        if first_trip {
+                            let _ = evt_tx.send(WatchdogEvent::Tripped { starved });
+                        }
+                    }
+
+                    recv(done_rx) -> _ => break,
+                }
+            }
+
+            // only the engagement of this watchdog is ours to release
+            if tripped {
+                loop_interlock.release();
+            }
+            debug!("watchdog loop stop");
+        });
+
+        Self {
+            feed_tx,
+            evt_rx,
+            interlock,
+            done_tx: Some(done_tx),
+            join: Some(join),
+        }
+    }
+
+    pub fn feed(&self) {
+        // a pending feed is as good as a new one
+        let _ = self.feed_tx.try_send(());
+    }
+
+    pub fn interlock(&self) -> Interlock {
+        self.interlock.clone()
+    }
+
+    pub fn receiver(&self) -> &Receiver<WatchdogEvent> {
+        &self.evt_rx
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        drop(self.done_tx.take());
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Pose estimation from wheel odometry fused with the chassis IMU yaw.
+//!
+//! Uses the frame of `kinematics`: x forward, y right in m, heading θ
+//! clockwise in degrees, all relative to the pose at the last reset.
+
+use std::thread;
+use std::time::Instant;
+
+use crossbeam_channel::{bounded, select, unbounded, Receiver, Sender, TrySendError};
+use tracing::debug;
+
+use crate::{
+    kinematics::{MecanumGeometry, WheelRpm, EP_GEOMETRY},
+    proto::v1::{
+        subject::{ChassisAttitude, EscStates},
+        subscribe::SubFreq,
+    },
+    Result, Robot,
+};
+
+/// Weight of the IMU yaw in each heading correction.
+pub const DEFAULT_YAW_GAIN: f32 = 0.1;
+
+// process noise per m travelled for x & y, and per degree turned for θ
+const WHEEL_NOISE_XY: f32 = 0.01;
+const WHEEL_NOISE_THETA: f32 = 0.05;
+// variance of the IMU yaw, deg²
+const IMU_YAW_NOISE: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pose {
+    pub x: f32,
+    pub y: f32,
+    pub theta: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoseEstimate {
+    pub pose: Pose,
+    /// covariance of (x, y, θ), in m² / m·deg / deg²
+    pub covariance: [[f32; 3]; 3],
+}
+
+/// A complementary filter: wheel odometry predicts the pose, the IMU yaw pulls
+/// the heading back, and the covariance is propagated alongside.
+#[derive(Debug, Clone)]
+pub struct OdometryEstimator {
+    geometry: MecanumGeometry,
+    yaw_gain: f32,
+    estimate: PoseEstimate,
+    yaw_offset: Option<f32>,
+}
+
+impl OdometryEstimator {
+    pub fn new(geometry: MecanumGeometry, yaw_gain: f32) -> Self {
+        Self {
+            geometry,
+            yaw_gain: yaw_gain.clamp(0.0, 1.0),
+            estimate: Default::default(),
+            yaw_offset: None,
+        }
+    }
+
+    /// Resets to the origin; the next IMU yaw becomes the zero heading.
+    pub fn reset(&mut self) {
+        self.estimate = Default::default();
+        self.yaw_offset = None;
+    }
+
+    pub fn estimate(&self) -> PoseEstimate {
+        self.estimate
+    }
+
+    /// Integrates wheel speeds held for `dt` seconds.
+    pub fn update_wheels(&mut self, wheels: WheelRpm, dt: f32) {
+        let twist = self.geometry.forward(wheels);
+        let (bx, by, dtheta) = (twist.x * dt, twist.y * dt, twist.z * dt);
+
+        let pose = &mut self.estimate.pose;
+        // integrate at the midpoint heading
+        let (sin, cos) = (pose.theta + dtheta / 2.0).to_radians().sin_cos();
+        let dx = bx * cos - by * sin;
+        let dy = bx * sin + by * cos;
+        pose.x += dx;
+        pose.y += dy;
+        pose.theta = wrap_degrees(pose.theta + dtheta);
+
+        // P = F P Fᵀ + Q, F being the jacobian w.r.t. (x, y, θ)
+        let jx = -dy.to_radians();
+        let jy = dx.to_radians();
+        let p = self.estimate.covariance;
+        let mut fp = p;
+        for j in 0..3 {
+            fp[0][j] = p[0][j] + jx * p[2][j];
+            fp[1][j] = p[1][j] + jy * p[2][j];
+        }
+
+        let mut next = fp;
+        for row in next.iter_mut() {
+            let r2 = row[2];
+            row[0] += r2 * jx;
+            row[1] += r2 * jy;
+        }
+
+        let dist = (bx * bx + by * by).sqrt();
+        next[0][0] += WHEEL_NOISE_XY * dist;
+        next[1][1] += WHEEL_NOISE_XY * dist;
+        next[2][2] += WHEEL_NOISE_THETA * dtheta.abs();
+        self.estimate.covariance = next;
+    }
+
+    /// Pulls the heading towards the IMU yaw, in degrees clockwise.
+    pub fn update_yaw(&mut self, yaw: f32) {
+        let theta = self.estimate.pose.theta;
+        let offset = *self.yaw_offset.get_or_insert(yaw - theta);
+
+        let measured = wrap_degrees(yaw - offset);
+        let pose = &mut self.estimate.pose;
+        let a = self.yaw_gain;
+        pose.theta = wrap_degrees(pose.theta + a * wrap_degrees(measured - pose.theta));
+
+        let p = &mut self.estimate.covariance;
+        p[2][2] = (1.0 - a) * (1.0 - a) * p[2][2] + a * a * IMU_YAW_NOISE;
+        p[0][2] *= 1.0 - a;
+        p[1][2] *= 1.0 - a;
+        p[2][0] *= 1.0 - a;
+        p[2][1] *= 1.0 - a;
+    }
+}
+
+impl Default for OdometryEstimator {
+    fn default() -> Self {
+        Self::new(EP_GEOMETRY, DEFAULT_YAW_GAIN)
+    }
+}
+
+pub(crate) fn wrap_degrees(v: f32) -> f32 {
+    let v = (v + 180.0).rem_euclid(360.0) - 180.0;
+    if v == -180.0 {
+        180.0
+    } else {
+        v
+    }
+}
+
+/// Runs an `OdometryEstimator` on subscribed ESC and attitude data, publishing
+/// an estimate for every ESC update. Only the latest estimate is kept, an
+/// unread one is replaced by the next.
+pub struct Odometry {
+    reset_tx: Sender<()>,
+    rx: Receiver<PoseEstimate>,
+    done_tx: Option<Sender<()>>,
+    join: Option<thread::JoinHandle<()>>,
+}
+
+impl Odometry {
+    pub fn start(robot: &Robot, freq: SubFreq, mut estimator: OdometryEstimator) -> Result<Self> {
+        let esc = robot.subscriber().subscribe::<EscStates>(freq)?;
+        let attitude = robot.subscriber().subscribe::<ChassisAttitude>(freq)?;
+
+        let (tx, rx) = bounded(1);
+        let latest_rx = rx.clone();
+        let (reset_tx, reset_rx) = unbounded();
+        let (done_tx, done_rx) = bounded::<()>(0);
+        let join = thread::spawn(move || {
+            debug!("odometry loop start");
+            let mut last_esc: Option<Instant> = None;
+            loop {
+                select! {
+                    recv(esc.receiver()) -> res => {
+                        let Ok(states) = res else {
+                            break;
+                        };
+
+                        let now = Instant::now();
+                        if let Some(last) = last_esc.replace(now) {
+                            let wheels = WheelRpm {
+                                w1: states.speed[0] as f32,
+                                w2: -states.speed[1] as f32,
+                                w3: -states.speed[2] as f32,
+                                w4: states.speed[3] as f32,
+                            };
+                            estimator.update_wheels(wheels, (now - last).as_secs_f32());
+                        }
+
+                        let mut estimate = estimator.estimate();
+                        while let Err(TrySendError::Full(e)) = tx.try_send(estimate) {
+                            // drop the stale estimate nobody has read yet
+                            let _ = latest_rx.try_recv();
+                            estimate = e;
+                        }
+                    }
+
+                    recv(attitude.receiver()) -> res => {
+                        let Ok(attitude) = res else {
+                            break;
+                        };
+
+                        estimator.update_yaw(attitude.yaw);
+                    }
+
+                    recv(reset_rx) -> _ => estimator.reset(),
+
+                    recv(done_rx) -> _ => break,
+                }
+            }
+            debug!("odometry loop stop");
+        });
+
+        Ok(Self {
+            reset_tx,
+            rx,
+            done_tx: Some(done_tx),
+            join: Some(join),
+        })
+    }
+
+    pub fn reset(&self) {
+        let _ = self.reset_tx.send(());
+    }
+
+    pub fn receiver(&self) -> &Receiver<PoseEstimate> {
+        &self.rx
+    }
+}
+
+impl Iterator for Odometry {
+    type Item = PoseEstimate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rx.recv().ok()
+    }
+}
+
+impl Drop for Odometry {
+    fn drop(&mut self) {
+        drop(self.done_tx.take());
+        if let Some(join) = self.join.take() {
+            let _ = join.join();
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub use robotic_arm::*;
 pub use servo::*;
 pub use sound::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct V1ActionResponse {
     pub retcode: RetCode,
     pub acception: Option<u8>,
@@ -24,11 +24,7 @@ pub struct V1ActionResponse {
 
 impl Completed for V1ActionResponse {
     fn is_completed(&self) -> bool {
-        State::from(V1ActionResponse {
-            retcode: self.retcode,
-            acception: self.acception,
-        })
-        .is_completed()
+        State::from(*self).is_completed()
     }
 }
 
@@ -1,7 +1,10 @@
-use std::io::Write;
+use std::io::{Cursor, Write};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 
 use crate::{
-    proto::{impl_empty_ser, v1::impl_v1_cmd, RetOK, Serialize},
+    ensure_buf_size, ensure_ok,
+    proto::{impl_empty_ser, v1::impl_v1_cmd, Deserialize, RetOK, Serialize},
     Result,
 };
 
@@ -32,7 +35,6 @@ impl_v1_cmd!(SetZoom, RetOK, 0x34);
 #[derive(Debug)]
 pub struct SetZoom {
     pub enable: bool,
-    _zoom: f64,
     pub typ: u8,
     pub value: i16,
 }
@@ -41,7 +43,6 @@ impl Default for SetZoom {
     fn default() -> Self {
         Self {
             enable: true,
-            _zoom: 1.0,
             typ: 1,
             value: 1,
         }
@@ -49,26 +50,45 @@ impl Default for SetZoom {
 }
 
 impl Serialize for SetZoom {
-    const SIZE: usize = 5;
+    const SIZE: usize = 6;
 
     fn ser(&self, w: &mut impl Write) -> Result<()> {
         let mut buf = [0u8; Self::SIZE];
         let enable_bit: u8 = self.enable.into();
         buf[0] = enable_bit << 3 | self.typ;
-        // this is just impossible
-        // buf[4..].copy_from_slice(&self.value.to_le_bytes());
-        w.write_all(&buf[..])?;
-        unimplemented!("this method is obviously incorrect");
+        Cursor::new(&mut buf[4..6]).write_i16::<LE>(self.value)?;
+        w.write_all(&buf[..]).map_err(From::from)
     }
 }
 
-impl_v1_cmd!(GetZoom, (), 0x35);
+impl_v1_cmd!(GetZoom, GetZoomResp, 0x35);
 
 #[derive(Default, Debug)]
 pub struct GetZoom;
 
 impl_empty_ser!(GetZoom);
 
+#[derive(Debug)]
+pub struct GetZoomResp {
+    pub enable: bool,
+    pub typ: u8,
+    pub value: i16,
+}
+
+impl Deserialize for GetZoomResp {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_ok!(buf);
+        ensure_buf_size!(buf, 1 + SetZoom::SIZE);
+        let value = Cursor::new(&buf[5..7]).read_i16::<LE>()?;
+
+        Ok(Self {
+            enable: (buf[1] >> 3) & 0x1 == 1,
+            typ: buf[1] & 0x7,
+            value,
+        })
+    }
+}
+
 impl_v1_cmd!(SetWhiteBalance, RetOK, 0x2c);
 
 #[repr(u8)]
@@ -97,3 +117,47 @@ impl Serialize for SetWhiteBalance {
         w.write_all(&buf[..]).map_err(From::from)
     }
 }
+
+impl_v1_cmd!(SetExposureMode, RetOK, 0x1e);
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum ExposureMode {
+    Auto = 1,
+    Manual = 4,
+}
+
+#[derive(Debug)]
+pub struct SetExposureMode {
+    pub mode: ExposureMode,
+}
+
+impl Serialize for SetExposureMode {
+    const SIZE: usize = 1;
+
+    fn ser(&self, w: &mut impl Write) -> Result<()> {
+        w.write_u8(self.mode as u8).map_err(From::from)
+    }
+}
+
+impl_v1_cmd!(SetEv, RetOK, 0x28);
+
+#[derive(Debug)]
+pub struct SetEv {
+    // 1/3 EV per step, 16 stands for 0EV
+    pub ev: u8,
+}
+
+impl Default for SetEv {
+    fn default() -> Self {
+        Self { ev: 16 }
+    }
+}
+
+impl Serialize for SetEv {
+    const SIZE: usize = 1;
+
+    fn ser(&self, w: &mut impl Write) -> Result<()> {
+        w.write_u8(self.ev).map_err(From::from)
+    }
+}
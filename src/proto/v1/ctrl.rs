@@ -11,7 +11,7 @@ use crate::{
     ensure_buf_size, ensure_ok,
     proto::{
         host2byte, impl_empty_ser,
-        v1::{impl_v1_action_cmd, impl_v1_cmd, impl_v1_event},
+        v1::{gripper, impl_v1_action_cmd, impl_v1_cmd, impl_v1_event},
         Deserialize, DussMBType, RetOK, Serialize,
     },
     Error, Result,
@@ -136,6 +136,35 @@ impl Serialize for ChassisStickOverlay {
     }
 }
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmorComp {
+    BottomBack = 0x1,
+    BottomFront = 0x2,
+    BottomLeft = 0x4,
+    BottomRight = 0x8,
+    BottomAll = 0xf,
+    TopLeft = 0x10,
+    TopRight = 0x20,
+    TopAll = 0x30,
+    All = 0x3f,
+}
+
+impl ArmorComp {
+    /// maps the 1-based armor index reported by hit events to its plate
+    pub fn from_index(index: u8) -> Option<Self> {
+        match index {
+            1 => Some(Self::BottomBack),
+            2 => Some(Self::BottomFront),
+            3 => Some(Self::BottomLeft),
+            4 => Some(Self::BottomRight),
+            5 => Some(Self::TopLeft),
+            6 => Some(Self::TopRight),
+            _ => None,
+        }
+    }
+}
+
 impl_v1_event!(ArmorHitEvent, 0x2);
 
 #[derive(Debug)]
@@ -204,16 +233,16 @@ impl_v1_cmd!(SetArmorParam, RetOK, 0x7);
 
 #[derive(Debug, Default)]
 pub struct SetArmorParam {
-    armor_mask: u8,
-    voice_energy_en: u16,
-    voice_energy_ex: u16,
-    voice_len_max: u16,
-    voice_len_min: u16,
-    voice_len_silence: u16,
-    voice_peak_count: u16,
-    voice_peak_min: u16,
-    voice_peak_ave: u16,
-    voice_peak_final: u16,
+    pub armor_mask: u8,
+    pub voice_energy_en: u16,
+    pub voice_energy_ex: u16,
+    pub voice_len_max: u16,
+    pub voice_len_min: u16,
+    pub voice_len_silence: u16,
+    pub voice_peak_count: u16,
+    pub voice_peak_min: u16,
+    pub voice_peak_ave: u16,
+    pub voice_peak_final: u16,
 }
 
 impl Serialize for SetArmorParam {
@@ -257,6 +286,26 @@ impl Serialize for ChassisWheelSpeed {
 
 impl_v1_cmd!(SetSystemLed, RetOK, 0x33);
 
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+pub enum LedComp {
+    ChassisFront = 0x1,
+    ChassisBack = 0x2,
+    ChassisLeft = 0x4,
+    ChassisRight = 0x8,
+    ChassisAll = 0xf,
+    GimbalLeft = 0x10,
+    GimbalRight = 0x20,
+    GimbalAll = 0x30,
+    All = 0x3f,
+}
+
+impl LedComp {
+    pub fn is_gimbal(&self) -> bool {
+        matches!(self, Self::GimbalLeft | Self::GimbalRight | Self::GimbalAll)
+    }
+}
+
 #[derive(Debug)]
 pub struct SetSystemLed {
     pub comp_mask: u32,
@@ -370,6 +419,13 @@ impl_empty_ser!(GetRobotMode);
 
 impl_v1_cmd!(BlasterFire, RetOK, 0x51);
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum FireType {
+    Water = 0,
+    Infrared = 1,
+}
+
 #[derive(Debug, Default)]
 pub struct BlasterFire {
     pub typ: u8,
@@ -386,6 +442,13 @@ impl Serialize for BlasterFire {
 
 impl_v1_cmd!(BlasterSetLed, RetOK, 0x55, DussMBType::Push);
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum BlasterLedEffect {
+    Off = 0,
+    On = 1,
+}
+
 #[derive(Debug)]
 pub struct BlasterSetLed {
     pub mode: u8,
@@ -423,14 +486,30 @@ impl Serialize for BlasterSetLed {
         buf[2] = self.g;
         buf[3] = self.b;
         buf[4] = self.times;
-        Cursor::new(&mut buf[4..6]).write_u16::<LE>(self.t1)?;
-        Cursor::new(&mut buf[6..8]).write_u16::<LE>(self.t2)?;
+        Cursor::new(&mut buf[5..7]).write_u16::<LE>(self.t1)?;
+        Cursor::new(&mut buf[7..9]).write_u16::<LE>(self.t2)?;
         w.write_all(&buf[..]).map_err(From::from)
     }
 }
 
 impl_v1_cmd!(StreamCtrl, RetOK, 0xd2);
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum StreamCtrlType {
+    SdkConn = 1,
+    Video = 2,
+    Audio = 3,
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum StreamResolution {
+    P720 = 0,
+    P360 = 1,
+    P540 = 2,
+}
+
 #[derive(Debug)]
 pub struct StreamCtrl {
     pub ctrl: u8,
@@ -881,6 +960,14 @@ impl Serialize for SetWheelSpeed {
 
 impl_v1_cmd!(ChassisSetWorkMode, RetOK, 0x19);
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum ChassisWorkMode {
+    Free = 0,
+    GimbalLead = 1,
+    ChassisLead = 2,
+}
+
 #[derive(Debug, Default)]
 pub struct ChassisSetWorkMode {
     pub mode: u8,
@@ -1029,7 +1116,8 @@ impl Serialize for ChassisSerialSet {
                 | (self.baud_rate as u8 & 0x7),
         )?;
 
-        // should be tx_enabled & rx_enabled?
+        // 0xff as in the sdk code linked above. how tx_enabled / rx_enabled
+        // map onto this byte is not documented, so they are not encoded
         w.write_u8(0xff)?;
 
         w.write_u16::<LE>(self.rx_size)?;
@@ -1068,6 +1156,28 @@ impl Serialize for ChassisSerialMsgSend {
     }
 }
 
+// data received by the chassis uart, pushed with the same ident as
+// `ChassisSerialMsgSend`
+impl_v1_event!(ChassisSerialMsgRecv, 0xc1);
+
+#[derive(Debug)]
+pub struct ChassisSerialMsgRecv {
+    pub typ: u8,
+    pub msg: Vec<u8>,
+}
+
+impl Deserialize for ChassisSerialMsgRecv {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 3);
+        let size = u16::from_le_bytes([buf[1], buf[2]]) as usize;
+        ensure_buf_size!(&buf[3..], size);
+        Ok(Self {
+            typ: buf[0],
+            msg: buf[3..3 + size].to_owned(),
+        })
+    }
+}
+
 impl_v1_cmd!(SensorGetData, SensorGetDataResp, 0xf0);
 
 #[derive(Debug)]
@@ -1182,7 +1292,7 @@ impl Default for RoboticArmMoveCtrl {
             action_id: 0,
             freq: ActionPushFreq::TenHz,
             action_ctrl: ActionCtrl::Start,
-            id: host2byte(27, 2),
+            id: gripper::ROBOTIC_ARM_ID,
             mode: 0,
             mask: 0x3,
             x: 0,
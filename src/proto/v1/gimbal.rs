@@ -3,7 +3,7 @@ use std::io::Write;
 use byteorder::{WriteBytesExt, LE};
 
 use crate::{
-    proto::{v1::impl_v1_cmd, RetOK, Serialize},
+    proto::{v1::impl_v1_cmd, DussMBType, RetOK, Serialize},
     Result,
 };
 
@@ -11,6 +11,14 @@ const CMD_SET: u8 = 0x4;
 
 impl_v1_cmd!(GimbalSetWorkMode, RetOK, 0x4c);
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum GimbalWorkMode {
+    Free = 0,
+    ChassisFollow = 1,
+    GimbalFollow = 2,
+}
+
 #[derive(Debug, Default)]
 pub struct GimbalSetWorkMode {
     pub workmode: u8,
@@ -28,6 +36,9 @@ impl Serialize for GimbalSetWorkMode {
 
 impl_v1_cmd!(GimbalCtrl, RetOK, 0xd);
 
+pub const GIMBAL_CTRL_SUSPEND: u16 = 0x2ab5;
+pub const GIMBAL_CTRL_RESUME: u16 = 0x7ef2;
+
 #[derive(Debug)]
 pub struct GimbalCtrl {
     pub order_code: u16,
@@ -35,7 +46,9 @@ pub struct GimbalCtrl {
 
 impl Default for GimbalCtrl {
     fn default() -> Self {
-        Self { order_code: 0x2ab5 }
+        Self {
+            order_code: GIMBAL_CTRL_SUSPEND,
+        }
     }
 }
 
@@ -46,3 +59,40 @@ impl Serialize for GimbalCtrl {
         w.write_u16::<LE>(self.order_code).map_err(From::from)
     }
 }
+
+impl_v1_cmd!(GimbalCtrlSpeed, RetOK, 0xc, DussMBType::Push);
+
+// see https://github.com/dji-sdk/RoboMaster-SDK/blob/8f301fd1bd3038f51c403614c52abbf9e9f5103c/src/robomaster/protocol.py
+#[derive(Debug)]
+pub struct GimbalCtrlSpeed {
+    pub yaw_speed: i16,   // Unit: 0.1 degree/s
+    pub roll_speed: i16,  // Unit: 0.1 degree/s
+    pub pitch_speed: i16, // Unit: 0.1 degree/s
+    pub ctrl_byte: u8,
+    pub ctrl_byte_extend: u8,
+}
+
+impl Default for GimbalCtrlSpeed {
+    fn default() -> Self {
+        Self {
+            yaw_speed: 0,
+            roll_speed: 0,
+            pitch_speed: 0,
+            ctrl_byte: 0xdc,
+            ctrl_byte_extend: 0,
+        }
+    }
+}
+
+impl Serialize for GimbalCtrlSpeed {
+    const SIZE: usize = 8;
+
+    fn ser(&self, w: &mut impl Write) -> Result<()> {
+        w.write_i16::<LE>(self.yaw_speed)?;
+        w.write_i16::<LE>(self.roll_speed)?;
+        w.write_i16::<LE>(self.pitch_speed)?;
+        w.write_u8(self.ctrl_byte)?;
+        w.write_u8(self.ctrl_byte_extend)?;
+        Ok(())
+    }
+}
@@ -10,8 +10,19 @@ use crate::{
 
 const CMD_SET: u8 = 0x33;
 
+pub const GRIPPER_ID: u8 = host2byte(27, 1);
+pub const ROBOTIC_ARM_ID: u8 = host2byte(27, 2);
+
 impl_v1_cmd!(GripperCtrl, RetOK, 0x11);
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum GripperCtrlType {
+    Pause = 0,
+    Open = 1,
+    Close = 2,
+}
+
 #[derive(Debug)]
 pub struct GripperCtrl {
     pub id: u8,
@@ -22,7 +33,7 @@ pub struct GripperCtrl {
 impl Default for GripperCtrl {
     fn default() -> Self {
         Self {
-            id: host2byte(27, 1),
+            id: GRIPPER_ID,
             control: 0,
             power: 330,
         }
@@ -56,7 +67,7 @@ pub struct RoboticArmMove {
 impl Default for RoboticArmMove {
     fn default() -> Self {
         Self {
-            id: host2byte(27, 1),
+            id: ROBOTIC_ARM_ID,
             typ: 0,
             mask: 0x3,
             x: 0,
@@ -111,7 +122,7 @@ pub struct RoboticArmGetPostion {
 
 impl Default for RoboticArmGetPostion {
     fn default() -> Self {
-        Self { id: 0x5b }
+        Self { id: ROBOTIC_ARM_ID }
     }
 }
 
@@ -122,7 +133,14 @@ impl Serialize for RoboticArmGetPostion {
     }
 }
 
-impl_v1_cmd!(ServoModeSet, (), 0x16);
+impl_v1_cmd!(ServoModeSet, RetOK, 0x16);
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy)]
+pub enum ServoMode {
+    Speed = 0,
+    Angle = 1,
+}
 
 #[derive(Debug)]
 pub struct ServoModeSet {
@@ -144,7 +162,7 @@ impl Serialize for ServoModeSet {
     }
 }
 
-impl_v1_cmd!(ServoControl, (), 0x17);
+impl_v1_cmd!(ServoControl, RetOK, 0x17);
 
 #[derive(Debug)]
 pub struct ServoControl {
@@ -179,19 +197,17 @@ impl_v1_cmd!(ServoGetAngle, ServoGetAngleResp, 0x15);
 
 #[derive(Debug)]
 pub struct ServoGetAngleResp {
-    pub angle: u32,
+    /// degrees, in the same frame as `ServoSetAngleAction`
+    pub angle: i32,
 }
 
 impl Deserialize for ServoGetAngleResp {
     fn de(buf: &[u8]) -> Result<Self> {
         ensure_ok!(buf);
         ensure_buf_size!(buf, 5);
+        let raw = Cursor::new(&buf[1..]).read_u32::<LE>()?;
         Ok(Self {
-            angle: (buf[1] as u32
-                + ((buf[2] as u32) << 8)
-                + ((buf[3] as u32) << 16)
-                + ((buf[4] as u32) << 24))
-                / 10,
+            angle: (raw / 10) as i32 - 180,
         })
     }
 }
@@ -16,6 +16,7 @@ pub mod ctrl;
 pub mod gimbal;
 pub mod gripper;
 pub mod normal;
+pub mod subject;
 pub mod subscribe;
 pub mod vision;
 
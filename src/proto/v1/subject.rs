@@ -0,0 +1,245 @@
+use std::io::Cursor;
+
+use byteorder::{ReadBytesExt, LE};
+
+use crate::{ensure_buf_size, proto::Deserialize, Error, Result};
+
+use super::subscribe::Subject;
+
+pub const TOF_SENSOR_NUM: usize = 4;
+
+/// Distances in mm reported by up to four TOF sensors, 0 if a sensor is
+/// absent or out of range.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TofDistances(pub [u16; TOF_SENSOR_NUM]);
+
+impl TofDistances {
+    /// `index` is 1-based, matching the id set on the sensor.
+    pub fn get(&self, index: usize) -> Option<u16> {
+        index
+            .checked_sub(1)
+            .and_then(|i| self.0.get(i))
+            .copied()
+            .filter(|d| *d != 0)
+    }
+}
+
+impl Subject for TofDistances {
+    const UID: u64 = 0x0002000986e4c05a;
+}
+
+impl Deserialize for TofDistances {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 2 * TOF_SENSOR_NUM);
+        let mut distances = [0u16; TOF_SENSOR_NUM];
+        for (d, chunk) in distances.iter_mut().zip(buf.chunks_exact(2)) {
+            *d = u16::from_le_bytes([chunk[0], chunk[1]]);
+        }
+
+        Ok(Self(distances))
+    }
+}
+
+/// Planar position of the robotic arm end point in mm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArmPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Subject for ArmPosition {
+    const UID: u64 = 0x0002000926abd64d;
+}
+
+impl Deserialize for ArmPosition {
+    fn de(buf: &[u8]) -> Result<Self> {
+        // leading mode & mask bytes are not needed
+        ensure_buf_size!(buf, 10);
+        let mut reader = Cursor::new(&buf[2..]);
+        let x = reader.read_i32::<LE>()?;
+        let y = reader.read_i32::<LE>()?;
+        Ok(Self { x, y })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GripperStatus {
+    /// neither fully opened nor fully closed, including while moving
+    InBetween,
+    Closed,
+    Opened,
+}
+
+impl Subject for GripperStatus {
+    const UID: u64 = 0x00020009124d156a;
+}
+
+impl Deserialize for GripperStatus {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 1);
+        match buf[0] {
+            0 => Ok(Self::InBetween),
+            1 => Ok(Self::Closed),
+            2 => Ok(Self::Opened),
+            other => Err(Error::InvalidData(
+                format!("unknown gripper status {}", other).into(),
+            )),
+        }
+    }
+}
+
+pub const SERVO_NUM: usize = 4;
+
+/// State of the servos, indexed by servo id - 1. Speeds are raw, angles are
+/// in degrees like `ServoGetAngleResp`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ServoStates {
+    pub valid: [bool; SERVO_NUM],
+    pub speed: [i16; SERVO_NUM],
+    pub angle: [i32; SERVO_NUM],
+}
+
+impl Subject for ServoStates {
+    const UID: u64 = 0x000200095f0059e7;
+}
+
+impl Deserialize for ServoStates {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 1 + 4 * SERVO_NUM);
+        let mut states = Self::default();
+        for (i, valid) in states.valid.iter_mut().enumerate() {
+            *valid = (buf[0] >> i) & 0x1 == 1;
+        }
+
+        let mut reader = Cursor::new(&buf[1..]);
+        for speed in states.speed.iter_mut() {
+            *speed = reader.read_i16::<LE>()?;
+        }
+
+        for angle in states.angle.iter_mut() {
+            *angle = (reader.read_u16::<LE>()? / 10) as i32 - 180;
+        }
+
+        Ok(states)
+    }
+}
+
+/// Chassis position in m and heading in degrees, relative to where the
+/// robot was powered on.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChassisPosition {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Subject for ChassisPosition {
+    const UID: u64 = 0x00020009eeb7cece;
+}
+
+impl Deserialize for ChassisPosition {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 12);
+        let mut reader = Cursor::new(buf);
+        let x = reader.read_f32::<LE>()?;
+        let y = reader.read_f32::<LE>()?;
+        let z = reader.read_f32::<LE>()?;
+        Ok(Self { x, y, z })
+    }
+}
+
+/// Chassis attitude in degrees.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChassisAttitude {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub roll: f32,
+}
+
+impl Subject for ChassisAttitude {
+    const UID: u64 = 0x000200096b986306;
+}
+
+impl Deserialize for ChassisAttitude {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 12);
+        let mut reader = Cursor::new(buf);
+        let yaw = reader.read_f32::<LE>()?;
+        let pitch = reader.read_f32::<LE>()?;
+        let roll = reader.read_f32::<LE>()?;
+        Ok(Self { yaw, pitch, roll })
+    }
+}
+
+/// Chassis velocity in m/s, `vg*` in the power-on frame and `vb*` in the
+/// body frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChassisVelocity {
+    pub vgx: f32,
+    pub vgy: f32,
+    pub vgz: f32,
+    pub vbx: f32,
+    pub vby: f32,
+    pub vbz: f32,
+}
+
+impl Subject for ChassisVelocity {
+    const UID: u64 = 0x0002000949a4009c;
+}
+
+impl Deserialize for ChassisVelocity {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 24);
+        let mut reader = Cursor::new(buf);
+        Ok(Self {
+            vgx: reader.read_f32::<LE>()?,
+            vgy: reader.read_f32::<LE>()?,
+            vgz: reader.read_f32::<LE>()?,
+            vbx: reader.read_f32::<LE>()?,
+            vby: reader.read_f32::<LE>()?,
+            vbz: reader.read_f32::<LE>()?,
+        })
+    }
+}
+
+pub const ESC_NUM: usize = 4;
+
+/// Raw motor controller readings, indexed by wheel id - 1. Speeds are motor
+/// rpm, so the mirrored left motors report negative values when driving
+/// forward.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EscStates {
+    pub speed: [i16; ESC_NUM],
+    pub angle: [i16; ESC_NUM],
+    pub timestamp: [u32; ESC_NUM],
+    pub state: [u8; ESC_NUM],
+}
+
+impl Subject for EscStates {
+    const UID: u64 = 0x00020009c14cb7c5;
+}
+
+impl Deserialize for EscStates {
+    fn de(buf: &[u8]) -> Result<Self> {
+        ensure_buf_size!(buf, 9 * ESC_NUM);
+        let mut states = Self::default();
+        let mut reader = Cursor::new(buf);
+        for speed in states.speed.iter_mut() {
+            *speed = reader.read_i16::<LE>()?;
+        }
+
+        for angle in states.angle.iter_mut() {
+            *angle = reader.read_i16::<LE>()?;
+        }
+
+        for timestamp in states.timestamp.iter_mut() {
+            *timestamp = reader.read_u32::<LE>()?;
+        }
+
+        for state in states.state.iter_mut() {
+            *state = reader.read_u8()?;
+        }
+
+        Ok(states)
+    }
+}
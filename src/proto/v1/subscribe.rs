@@ -13,6 +13,21 @@ use crate::{
 
 const CMD_SET: u8 = 0x48;
 
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub enum SubFreq {
+    OneHz = 1,
+    FiveHz = 5,
+    TenHz = 10,
+    TwentyHz = 20,
+    FiftyHz = 50,
+}
+
+/// Data that can be subscribed to, delivered through `PushPeriodMsg`.
+pub trait Subject: Deserialize + Send + 'static {
+    const UID: u64;
+}
+
 impl_v1_cmd!(SubscribeAddNode, SubscribeAddNodeResp, 0x01);
 
 #[derive(Debug)]
@@ -107,25 +107,113 @@ impl Serialize for VisionSetColor {
 impl_v1_cmd!(VisionDetectEnable, RetOK, 0xa3);
 
 #[derive(Debug)]
-pub struct VisionDetectEnable(pub VisionType);
+pub struct VisionDetectEnable(pub VisionTypeMask);
 
 impl Serialize for VisionDetectEnable {
     const SIZE: usize = 2;
 
     fn ser(&self, w: &mut impl Write) -> Result<()> {
-        w.write_u16::<LE>(self.0 as u16).map_err(From::from)
+        w.write_u16::<LE>(self.0 .0).map_err(From::from)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Forward,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Stop,
+    Dice,
+    Target,
+    Arrow(Direction),
+    Heart,
+    Number(u8),
+    Letter(char),
+    Unknown(u16),
+}
+
+impl From<u16> for Marker {
+    fn from(v: u16) -> Self {
+        match v {
+            1 => Marker::Stop,
+            2 => Marker::Dice,
+            3 => Marker::Target,
+            4 => Marker::Arrow(Direction::Left),
+            5 => Marker::Arrow(Direction::Right),
+            6 => Marker::Arrow(Direction::Forward),
+            8 => Marker::Heart,
+            10..=19 => Marker::Number((v - 10) as u8),
+            20..=45 => Marker::Letter((b'A' + (v - 20) as u8) as char),
+            other => Marker::Unknown(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    Jump,
+    LeftHandUp,
+    RightHandUp,
+    Victory,
+    Capture,
+    Unknown(u32),
+}
+
+impl From<u32> for Gesture {
+    fn from(v: u32) -> Self {
+        match v {
+            1 => Gesture::Jump,
+            2 => Gesture::LeftHandUp,
+            3 => Gesture::RightHandUp,
+            4 => Gesture::Victory,
+            5 => Gesture::Capture,
+            other => Gesture::Unknown(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineType {
+    None,
+    Straight,
+    Fork,
+    Cross,
+    Unknown(u32),
+}
+
+impl From<u32> for LineType {
+    fn from(v: u32) -> Self {
+        match v {
+            0 => LineType::None,
+            1 => LineType::Straight,
+            2 => LineType::Fork,
+            3 => LineType::Cross,
+            other => LineType::Unknown(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinePoint {
+    pub x: f32,
+    pub y: f32,
+    pub theta: f32,
+    pub curvature: f32,
+}
+
 impl_v1_event!(VisionDetectInfo, 0xa4);
 
 #[derive(Debug)]
 pub enum VisionRectInfo {
     Shoulder(Vec<[f32; 4]>),
     Person(Vec<[f32; 4]>),
-    Gesture(Vec<([f32; 4], u32)>),
-    Line(u32, Vec<([f32; 4])>),
-    Marker(Vec<([f32; 4], u16)>),
+    Gesture(Vec<([f32; 4], Gesture)>),
+    Line(LineType, Vec<LinePoint>),
+    Marker(Vec<([f32; 4], Marker)>),
     Robot(Vec<[f32; 4]>),
 }
 
@@ -184,15 +272,18 @@ impl Deserialize for VisionDetectInfo {
                         let w = reader.read_f32::<LE>()?;
                         let h = reader.read_f32::<LE>()?;
                         let info = reader.read_u32::<LE>()?;
-                        Ok(([round(x, 5), round(y, 5), round(w, 5), round(h, 5)], info))
+                        Ok((
+                            [round(x, 5), round(y, 5), round(w, 5), round(h, 5)],
+                            info.into(),
+                        ))
                     })
                     .collect::<Result<Vec<_>>>()?;
                 VisionRectInfo::Gesture(rects)
             }
 
             VisionType::Line => {
-                let mut ident = 0;
-                let mut rects = vec![];
+                let mut line_type = LineType::None;
+                let mut points = vec![];
                 for (i, data) in chunks.enumerate() {
                     let mut reader = Cursor::new(data);
                     let x = reader.read_f32::<LE>()?;
@@ -201,12 +292,17 @@ impl Deserialize for VisionDetectInfo {
                     let c = reader.read_f32::<LE>()?;
                     if i == 0 {
                         let info = reader.read_u32::<LE>()?;
-                        ident = info;
+                        line_type = info.into();
                     }
 
-                    rects.push([round(x, 7), round(y, 7), round(theta, 7), round(c, 7)]);
+                    points.push(LinePoint {
+                        x: round(x, 7),
+                        y: round(y, 7),
+                        theta: round(theta, 7),
+                        curvature: round(c, 7),
+                    });
                 }
-                VisionRectInfo::Line(ident, rects)
+                VisionRectInfo::Line(line_type, points)
             }
 
             VisionType::Marker => {
@@ -218,7 +314,10 @@ impl Deserialize for VisionDetectInfo {
                         let w = reader.read_f32::<LE>()?;
                         let h = reader.read_f32::<LE>()?;
                         let info = reader.read_u16::<LE>()?;
-                        Ok(([round(x, 5), round(y, 5), round(w, 5), round(h, 5)], info))
+                        Ok((
+                            [round(x, 5), round(y, 5), round(w, 5), round(h, 5)],
+                            info.into(),
+                        ))
                     })
                     .collect::<Result<Vec<_>>>()?;
 
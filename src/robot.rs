@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::{
+    conn::Client,
+    modules::{subscriber::Subscriber, watchdog::Interlock},
+    proto::v1::V1,
+};
+
+pub struct Robot {
+    client: Arc<Client<V1>>,
+    subscriber: Arc<Subscriber>,
+    interlock: Interlock,
+}
+
+impl Robot {
+    pub fn new(client: Client<V1>) -> Self {
+        let client = Arc::new(client);
+        Self {
+            subscriber: Arc::new(Subscriber::new(client.clone())),
+            client,
+            interlock: Interlock::default(),
+        }
+    }
+
+    pub fn client(&self) -> &Arc<Client<V1>> {
+        &self.client
+    }
+
+    pub fn subscriber(&self) -> &Arc<Subscriber> {
+        &self.subscriber
+    }
+
+    pub fn interlock(&self) -> &Interlock {
+        &self.interlock
+    }
+}
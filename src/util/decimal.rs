@@ -29,7 +29,7 @@ macro_rules! impl_maybe_round_default {
     };
 }
 
-impl_maybe_round_default!(i16, u16);
+impl_maybe_round_default!(i16, u16, u8);
 
 impl MaybeRound for f32 {
     fn round(self, digits: i32) -> Result<Self> {
@@ -82,7 +82,7 @@ macro_rules! impl_maybe_move {
     };
 }
 
-impl_maybe_move!(f32, i16, u16);
+impl_maybe_move!(f32, i16, u16, u8);
 
 macro_rules! impl_maybe_as {
     ($t1:ty, $t2:ty) => {
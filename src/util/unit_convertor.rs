@@ -125,6 +125,42 @@ pub const GIMBAL_YAW_MOVE_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
     unit: "°",
 };
 
+pub const GIMBAL_PITCH_TARGET_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(-25.0),
+    end: Some(30.0),
+    decimal: 0,
+    scale: 10.0,
+    delta: 0.0,
+    unit: "°",
+};
+
+pub const GIMBAL_YAW_TARGET_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(-250.0),
+    end: Some(250.0),
+    decimal: 0,
+    scale: 10.0,
+    delta: 0.0,
+    unit: "°",
+};
+
+pub const GIMBAL_PITCH_SPEED_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(-360.0),
+    end: Some(360.0),
+    decimal: 0,
+    scale: 10.0,
+    delta: 0.0,
+    unit: "°/s",
+};
+
+pub const GIMBAL_YAW_SPEED_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(-360.0),
+    end: Some(360.0),
+    decimal: 0,
+    scale: 10.0,
+    delta: 0.0,
+    unit: "°/s",
+};
+
 pub const GIMBAL_PITCH_MOVE_SPEED_SET_CONVERTOR: UnitConvertor<u16> = UnitConvertor {
     start: Some(0),
     end: Some(540),
@@ -143,12 +179,94 @@ pub const GIMBAL_YAW_MOVE_SPEED_SET_CONVERTOR: UnitConvertor<u16> = UnitConverto
     unit: "°/s",
 };
 
+pub const BLASTER_FIRE_TIMES_CONVERTOR: UnitConvertor<u8> = UnitConvertor {
+    start: Some(1),
+    end: Some(8),
+    decimal: 0,
+    scale: 1,
+    delta: 0,
+    unit: "",
+};
+
+pub const ARMOR_HIT_SENSITIVITY_CONVERTOR: UnitConvertor<u16> = UnitConvertor {
+    start: Some(0),
+    end: Some(10),
+    decimal: 0,
+    scale: 28,
+    delta: 0,
+    unit: "",
+};
+
+pub const GRIPPER_POWER_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(1.0),
+    end: Some(100.0),
+    decimal: 0,
+    scale: 3.3,
+    delta: 0.0,
+    unit: "%",
+};
+
+pub const SERVO_SPEED_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(-49.0),
+    end: Some(49.0),
+    decimal: 0,
+    scale: 1.0,
+    delta: 49.0,
+    unit: "",
+};
+
+pub const LED_FLASH_FREQ_CONVERTOR: UnitConvertor<u8> = UnitConvertor {
+    start: Some(1),
+    end: Some(10),
+    decimal: 0,
+    scale: 1,
+    delta: 0,
+    unit: "Hz",
+};
+
+pub const CAMERA_ZOOM_CONVERTOR: UnitConvertor<i16> = UnitConvertor {
+    start: Some(1),
+    end: Some(4),
+    decimal: 0,
+    scale: 1,
+    delta: 0,
+    unit: "x",
+};
+
+pub const CAMERA_WHITE_BALANCE_TEMP_CONVERTOR: UnitConvertor<u16> = UnitConvertor {
+    start: Some(2000),
+    end: Some(10000),
+    decimal: 0,
+    scale: 1,
+    delta: 0,
+    unit: "K",
+};
+
+pub const CAMERA_WHITE_BALANCE_TINT_CONVERTOR: UnitConvertor<i16> = UnitConvertor {
+    start: Some(-50),
+    end: Some(50),
+    decimal: 0,
+    scale: 1,
+    delta: 0,
+    unit: "",
+};
+
+pub const CAMERA_EV_CONVERTOR: UnitConvertor<f32> = UnitConvertor {
+    start: Some(-3.0),
+    end: Some(3.0),
+    decimal: 0,
+    scale: 3.0,
+    delta: 16.0,
+    unit: "EV",
+};
+
 pub struct UnitConvertor<V> {
     start: Option<V>,
     end: Option<V>,
     decimal: i32,
     scale: V,
     delta: V,
+    #[allow(dead_code)]
     unit: &'static str,
 }
 
@@ -172,6 +290,10 @@ where
         v
     }
 
+    pub fn bounds(&self) -> (Option<V>, Option<V>) {
+        (self.start, self.end)
+    }
+
     pub fn val2proto<PV: MaybeFrom<V>>(&self, mut v: V) -> Result<PV> {
         v = self.check(v);
         v *= self.scale;
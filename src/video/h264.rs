@@ -0,0 +1,250 @@
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crate::Result;
+
+pub const NAL_TYPE_SLICE: u8 = 1;
+pub const NAL_TYPE_IDR: u8 = 5;
+pub const NAL_TYPE_SEI: u8 = 6;
+pub const NAL_TYPE_SPS: u8 = 7;
+pub const NAL_TYPE_PPS: u8 = 8;
+pub const NAL_TYPE_AUD: u8 = 9;
+
+const START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+#[derive(Debug, Clone)]
+pub struct NalUnit(Vec<u8>);
+
+impl NalUnit {
+    // data without start code
+    pub fn data(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn nal_type(&self) -> u8 {
+        self.0[0] & 0x1f
+    }
+
+    pub fn is_vcl(&self) -> bool {
+        (NAL_TYPE_SLICE..=NAL_TYPE_IDR).contains(&self.nal_type())
+    }
+
+    // first_mb_in_slice is ue(v) coded, a leading 1 bit means 0
+    fn is_first_slice(&self) -> bool {
+        self.is_vcl() && self.0.len() > 1 && self.0[1] & 0x80 != 0
+    }
+}
+
+// Splits an Annex-B byte stream into NAL units.
+#[derive(Debug, Default)]
+pub struct NalSplitter {
+    buf: Vec<u8>,
+    scan_pos: usize,
+    started: bool,
+}
+
+impl NalSplitter {
+    pub fn push(&mut self, data: &[u8]) -> Vec<NalUnit> {
+        self.buf.extend_from_slice(data);
+
+        let mut nals = Vec::new();
+        let mut nal_start = 0;
+        let mut i = self.scan_pos;
+        while i + 3 <= self.buf.len() {
+            if self.buf[i] != 0 || self.buf[i + 1] != 0 || self.buf[i + 2] != 1 {
+                i += 1;
+                continue;
+            }
+
+            if self.started {
+                // trailing zeros belong to a 4-byte start code or trailing_zero_8bits
+                let mut end = i;
+                while end > nal_start && self.buf[end - 1] == 0 {
+                    end -= 1;
+                }
+
+                if end > nal_start {
+                    nals.push(NalUnit(self.buf[nal_start..end].to_vec()));
+                }
+            }
+
+            self.started = true;
+            i += 3;
+            nal_start = i;
+        }
+
+        if !self.started {
+            // no start code yet, keep the last bytes which may be a partial one
+            nal_start = self.buf.len().saturating_sub(2);
+            i = nal_start;
+        }
+
+        self.buf.drain(..nal_start);
+        self.scan_pos = i - nal_start;
+        nals
+    }
+
+    // Returns the last NAL unit, which has no start code after it, at the
+    // end of the stream.
+    pub fn flush(&mut self) -> Option<NalUnit> {
+        let mut buf = std::mem::take(&mut self.buf);
+        self.scan_pos = 0;
+        if !std::mem::take(&mut self.started) {
+            return None;
+        }
+
+        while buf.last() == Some(&0) {
+            buf.pop();
+        }
+
+        if buf.is_empty() {
+            None
+        } else {
+            Some(NalUnit(buf))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AccessUnit {
+    nals: Vec<NalUnit>,
+    keyframe: bool,
+    timestamp: Duration,
+}
+
+impl AccessUnit {
+    pub fn nals(&self) -> &[NalUnit] {
+        &self.nals
+    }
+
+    pub fn is_keyframe(&self) -> bool {
+        self.keyframe
+    }
+
+    // time of arrival, relative to the start of the stream
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    pub fn write_annexb(&self, w: &mut impl Write) -> Result<()> {
+        for nal in self.nals.iter() {
+            w.write_all(&START_CODE[..])?;
+            w.write_all(nal.data())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_annexb(&self) -> Vec<u8> {
+        let size = self.nals.iter().map(|n| START_CODE.len() + n.0.len()).sum();
+        let mut buf = Vec::with_capacity(size);
+        for nal in self.nals.iter() {
+            buf.extend_from_slice(&START_CODE[..]);
+            buf.extend_from_slice(nal.data());
+        }
+
+        buf
+    }
+}
+
+// Groups NAL units into access units, keeping track of the latest SPS/PPS so
+// that every keyframe can be decoded on its own.
+#[derive(Debug)]
+pub struct AccessUnitAssembler {
+    epoch: Instant,
+    sps: Option<NalUnit>,
+    pps: Option<NalUnit>,
+    synced: bool,
+    pending: Vec<NalUnit>,
+    pending_at: Option<Instant>,
+    has_vcl: bool,
+}
+
+impl AccessUnitAssembler {
+    pub fn new(epoch: Instant) -> Self {
+        Self {
+            epoch,
+            sps: None,
+            pps: None,
+            synced: false,
+            pending: Vec::new(),
+            pending_at: None,
+            has_vcl: false,
+        }
+    }
+
+    pub fn sps(&self) -> Option<&NalUnit> {
+        self.sps.as_ref()
+    }
+
+    pub fn pps(&self) -> Option<&NalUnit> {
+        self.pps.as_ref()
+    }
+
+    pub fn push(&mut self, nal: NalUnit, at: Instant) -> Option<AccessUnit> {
+        let nal_type = nal.nal_type();
+        let boundary = self.has_vcl
+            && (matches!(
+                nal_type,
+                NAL_TYPE_AUD | NAL_TYPE_SPS | NAL_TYPE_PPS | NAL_TYPE_SEI
+            ) || nal.is_first_slice());
+
+        let finished = if boundary { self.finish() } else { None };
+
+        match nal_type {
+            NAL_TYPE_SPS => self.sps = Some(nal.clone()),
+            NAL_TYPE_PPS => self.pps = Some(nal.clone()),
+            _ => {}
+        }
+
+        self.has_vcl |= nal.is_vcl();
+        self.pending_at.get_or_insert(at);
+        self.pending.push(nal);
+
+        finished
+    }
+
+    // Returns the pending access unit at the end of the stream.
+    pub fn flush(&mut self) -> Option<AccessUnit> {
+        if !self.has_vcl {
+            return None;
+        }
+
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Option<AccessUnit> {
+        let mut nals = std::mem::take(&mut self.pending);
+        let at = self.pending_at.take()?;
+        self.has_vcl = false;
+
+        let keyframe = nals.iter().any(|n| n.nal_type() == NAL_TYPE_IDR);
+        if keyframe {
+            let (sps, pps) = match (self.sps.as_ref(), self.pps.as_ref()) {
+                (Some(sps), Some(pps)) => (sps, pps),
+                _ => return None,
+            };
+
+            if !nals.iter().any(|n| n.nal_type() == NAL_TYPE_PPS) {
+                nals.insert(0, pps.clone());
+            }
+
+            if !nals.iter().any(|n| n.nal_type() == NAL_TYPE_SPS) {
+                nals.insert(0, sps.clone());
+            }
+
+            self.synced = true;
+        }
+
+        // frames before the first keyframe can not be decoded
+        if !self.synced {
+            return None;
+        }
+
+        Some(AccessUnit {
+            nals,
+            keyframe,
+            timestamp: at.saturating_duration_since(self.epoch),
+        })
+    }
+}
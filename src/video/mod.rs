@@ -0,0 +1,71 @@
+use std::net::IpAddr;
+use std::time::Instant;
+
+use crossbeam_channel::Receiver;
+
+use crate::{
+    conn::MediaStream,
+    proto::v1::ctrl::{StreamCtrlType, StreamResolution},
+    Result, Robot,
+};
+
+pub mod h264;
+
+pub use h264::{AccessUnit, NalUnit};
+
+pub const VIDEO_STREAM_PORT: u16 = 40921;
+
+const ACCESS_UNIT_CHAN_SIZE: usize = 64;
+
+pub struct VideoStream {
+    stream: MediaStream<AccessUnit>,
+}
+
+impl VideoStream {
+    pub fn start(robot: &Robot, ip: IpAddr, resolution: StreamResolution) -> Result<Self> {
+        let mut splitter = h264::NalSplitter::default();
+        let mut assembler = h264::AccessUnitAssembler::new(Instant::now());
+        let stream = MediaStream::start(
+            robot,
+            ip,
+            VIDEO_STREAM_PORT,
+            StreamCtrlType::Video,
+            resolution as u8,
+            ACCESS_UNIT_CHAN_SIZE,
+            move |data: Option<&[u8]>, now| {
+                let mut aus = Vec::new();
+                match data {
+                    Some(data) => {
+                        for nal in splitter.push(data) {
+                            aus.extend(assembler.push(nal, now));
+                        }
+                    }
+
+                    // flush the last access unit, there is no boundary after it
+                    None => {
+                        if let Some(nal) = splitter.flush() {
+                            aus.extend(assembler.push(nal, now));
+                        }
+                        aus.extend(assembler.flush());
+                    }
+                }
+
+                Ok(aus)
+            },
+        )?;
+
+        Ok(Self { stream })
+    }
+
+    pub fn receiver(&self) -> &Receiver<AccessUnit> {
+        self.stream.receiver()
+    }
+}
+
+impl Iterator for VideoStream {
+    type Item = AccessUnit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stream.receiver().recv().ok()
+    }
+}